@@ -178,7 +178,7 @@ static const Key keys[] = {
     {MODKEY, XK_u, spawn,
      SHCMD("maim --select | xclip -selection clipboard -t image/png")},
 
-    {MODKEY, XK_slash, spawn, SHCMD("dwm-cheatsheet")},
+    {MODKEY, XK_slash, spawn, SHCMD("dwm-cheatsheet toggle")},
     {MODKEY, XK_b, spawn, SHCMD("firefox")},
     {MODKEY, XK_e, spawn, SHCMD("pcmanfm")},
     {MODKEY | ShiftMask, XK_e, spawn, SHCMD("nvim")},
@@ -0,0 +1,204 @@
+use crate::deps;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Starts `argv` detached from the cheatsheet, like dwm's `spawn`: in its own
+/// process group with no inherited stdio, so callers such as rofi don't wait on it.
+pub fn spawn(argv: &[String]) -> io::Result<()> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty command"))?;
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn()
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", program, err)))?;
+
+    // Reap the child whenever it exits so long-running callers don't collect zombies
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Editors that need a terminal to run in; emacs only does with `-nw`.
+const TERMINAL_EDITORS: [&str; 8] = ["vi", "vim", "nvim", "nano", "micro", "hx", "helix", "kak"];
+
+/// Terminals tried, in order, when `$TERMINAL` isn't set.
+const TERMINALS: [&str; 4] = ["st", "alacritty", "kitty", "xterm"];
+
+/// `$VISUAL` or `$EDITOR` opening `path` at `line`, and whether it needs a
+/// terminal; `None` when neither is set.
+///
+/// Most editors take `+N file`; VS Code and Sublime take `-g file:N` and
+/// Helix `file:N`.
+fn editor_command(path: &Path, line: Option<usize>) -> Option<(Vec<String>, bool)> {
+    let file = path.display().to_string();
+    let editor = env::var("VISUAL").or_else(|_| env::var("EDITOR")).unwrap_or_default();
+    let mut argv: Vec<String> = editor.split_whitespace().map(String::from).collect();
+    let program = argv.first().map(|program| program.rsplit('/').next().unwrap_or(program).to_string())?;
+
+    match (program.as_str(), line) {
+        ("code" | "codium" | "subl", Some(line)) => argv.extend(["-g".to_string(), format!("{}:{}", file, line)]),
+        ("hx" | "helix", Some(line)) => argv.push(format!("{}:{}", file, line)),
+        (_, Some(line)) => argv.extend([format!("+{}", line), file]),
+        (_, None) => argv.push(file),
+    }
+    let in_terminal = match program.as_str() {
+        "emacs" => argv.iter().any(|arg| arg == "-nw"),
+        program => TERMINAL_EDITORS.contains(&program),
+    };
+    Some((argv, in_terminal))
+}
+
+/// Opens `path` in `$VISUAL` or `$EDITOR`, at `line` when given, inside a
+/// terminal for editors that need one. Without either variable the file goes to
+/// `xdg-open`.
+pub fn open_in_editor(path: &Path, line: Option<usize>) -> io::Result<()> {
+    let Some((mut argv, in_terminal)) = editor_command(path, line) else {
+        return spawn(&["xdg-open".to_string(), path.display().to_string()]);
+    };
+    if in_terminal {
+        let terminal = env::var("TERMINAL")
+            .ok()
+            .or_else(|| TERMINALS.iter().find(|t| deps::resolve(t).is_some()).map(|t| t.to_string()))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no terminal found to run {}", argv[0])))?;
+        argv.splice(0..0, [terminal, "-e".to_string()]);
+    }
+    spawn(&argv)
+}
+
+/// Like [`open_in_editor`], but from a terminal a terminal editor takes it over
+/// until it exits.
+pub fn edit_from_terminal(path: &Path, line: Option<usize>) -> io::Result<()> {
+    match editor_command(path, line) {
+        Some((argv, true)) if io::stdin().is_terminal() => {
+            Command::new(&argv[0])
+                .args(&argv[1..])
+                .status()
+                .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", argv[0], err)))?;
+            Ok(())
+        }
+        _ => open_in_editor(path, line),
+    }
+}
+
+/// Clipboard helpers tried in order; the first one that runs wins.
+const CLIPBOARD_COMMANDS: [&[&str]; 3] = [
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+    &["wl-copy"],
+];
+
+/// Puts `text` on the clipboard through an external helper, which keeps owning the
+/// selection after the cheatsheet exits.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, "no clipboard helper found");
+    for command in CLIPBOARD_COMMANDS {
+        let child = Command::new(command[0])
+            .args(&command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match child {
+            Ok(mut child) => {
+                child
+                    .stdin
+                    .take()
+                    .expect("stdin is piped")
+                    .write_all(text.as_bytes())?;
+                child.wait()?;
+                return Ok(());
+            }
+            Err(err) => last_error = err,
+        }
+    }
+    Err(last_error)
+}
+
+/// Programs that end sessions or processes, lock the screen or suspend the
+/// machine; running them from a click deserves a second look.
+const DESTRUCTIVE_PROGRAMS: [&str; 19] = [
+    "killall", "pkill", "kill", "xkill", "reboot", "poweroff", "shutdown", "halt", "systemctl", "loginctl",
+    "slock", "i3lock", "swaylock", "xsecurelock", "physlock", "xlock", "betterlockscreen", "multilockscreen",
+    "wlogout",
+];
+
+/// Starts of program names, or of their `-`, `_` or `.` separated parts, that
+/// mark lockers, power menus and restarts (`lock.sh`, `powermenu`, `dwm-restart`).
+const DESTRUCTIVE_FRAGMENTS: [&str; 6] = ["lock", "power", "restart", "suspend", "hibernate", "logout"];
+
+/// dwm functions that end the session or close a window.
+const DESTRUCTIVE_FUNCTIONS: [&str; 2] = ["quit", "killclient"];
+
+/// Whether a binding calling the dwm function `function` should only be
+/// triggered after the user confirms it.
+pub fn is_destructive_function(function: &str) -> bool {
+    DESTRUCTIVE_FUNCTIONS.contains(&function)
+}
+
+/// Whether `argv` should only run after the user confirms it. Only the programs
+/// it runs are looked at, so `rofi -theme power.rasi` needs no confirmation.
+pub fn needs_confirmation(argv: &[String]) -> bool {
+    deps::command_programs(argv).iter().any(|program| {
+        let name = program.rsplit('/').next().unwrap_or(program).to_lowercase();
+        name.split(['-', '_', '.']).any(|part| {
+            DESTRUCTIVE_PROGRAMS.contains(&part) || DESTRUCTIVE_FRAGMENTS.iter().any(|f| part.starts_with(f))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn sh(line: &str) -> Vec<String> {
+        argv(&["/bin/sh", "-c", line])
+    }
+
+    #[test]
+    fn session_and_process_enders_need_confirmation() {
+        for command in [
+            argv(&["killall", "dwm"]),
+            argv(&["/usr/bin/pkill", "-x", "picom"]),
+            argv(&["betterlockscreen", "-l"]),
+            argv(&["slock"]),
+            argv(&["i3lock-fancy"]),
+            argv(&["systemctl", "suspend"]),
+            argv(&["loginctl", "lock-session"]),
+            argv(&["/home/me/.local/bin/powermenu"]),
+            argv(&["rofi-power-menu"]),
+            argv(&["dwm-restart"]),
+            sh("~/scripts/lock.sh"),
+            sh("setxkbmap us && killall -USR1 dwmblocks"),
+            sh("notify-send bye; systemctl poweroff"),
+        ] {
+            assert!(needs_confirmation(&command), "{:?}", command);
+        }
+    }
+
+    #[test]
+    fn arguments_alone_do_not_need_confirmation() {
+        for command in [
+            argv(&["gnome-clocks"]),
+            argv(&["xdotool", "key", "Caps_Lock"]),
+            argv(&["rofi", "-theme", "power.rasi", "-show", "drun"]),
+            argv(&["dunstctl", "close-all"]),
+            argv(&["firefox", "https://example.com/restart"]),
+            sh("maim --select | xclip -selection clipboard -t image/png"),
+            sh("pactl set-sink-mute @DEFAULT_SINK@ toggle && notify-send 'Lock keys' 2>/dev/null"),
+        ] {
+            assert!(!needs_confirmation(&command), "{:?}", command);
+        }
+    }
+}
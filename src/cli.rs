@@ -0,0 +1,418 @@
+use crate::compare::{self, DiffOptions};
+use crate::keybindings::Category;
+use crate::monitor::WindowGeometry;
+use crate::pdf::{Paper, PdfOptions};
+use crate::ui::WindowOptions;
+use crate::wallpaper::{self, Background, Format, Position, WallpaperOptions};
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage: dwm-cheatsheet [--config FILE] [COMMAND] [OPTIONS]
+
+Commands:
+  (none)            Open the cheatsheet window, or show the daemon's if one is running
+  daemon            Keep the cheatsheet resident with its window hidden until toggled
+  toggle            Show the daemon's window, or hide it when shown; starts the daemon if needed
+  show              Show the daemon's window; starts the daemon if needed
+  pdf               Export a printable PDF
+  wallpaper         Render the cheatsheet as a PNG or SVG wallpaper
+  dmenu             Print one line per binding for dmenu
+  rofi              Run as a rofi script mode (rofi -modi \"keys:dwm-cheatsheet rofi\")
+  send-keys COMBO   Replay a combo such as Super+Shift+f to the X server via XTEST
+  run-command COMBO Run the binding for COMBO inside dwm via the IPC patch's run_command
+  edit COMBO        Open the config in $VISUAL or $EDITOR at the line binding COMBO
+  state             Print dwm's current tags, layout and focused client (needs the IPC patch)
+  deps              List programs the bindings run that aren't installed, with package hints
+  diff OLD NEW      Compare the bindings and settings of two configs, each a path or
+                    git:<rev>:<path> (e.g. git:HEAD~1:config.h)
+
+Global options:
+      --config PATH     dwm config.h to read actions from, or a directory to read its config.h
+                        or else config.def.h from (default: ~/.config/chadwm/chadwm/config.h,
+                        then config.def.h beside it, then the bundled config.def.h)
+
+Window options (none, daemon, toggle, show):
+      --overlay         Borderless, always-on-top popup over the focused monitor that closes
+                        on focus loss or a key it doesn't use
+      --timeout SECS    Close the overlay after SECS without input
+      --class CLASS     WM_CLASS class for dwm rules (default: dwm-cheatsheet)
+      --name NAME       WM_CLASS instance for dwm rules (default: dwm-cheatsheet)
+      --geometry GEOM   Window size and position as WxH+X+Y, WxH or +X+Y; negative offsets
+                        count from the right and bottom edges
+      --monitor N       Open on monitor N (0 is the first), positioning relative to it
+      --reset-state     Forget the saved window placement and UI state
+      --search TEXT     Search for TEXT when the window appears
+      --category NAME   Only show the category NAME (media, screenshots, applications,
+                        window-management, navigation, layout, gaps, tags, system, borders);
+                        repeat for several
+      --modifier MOD    Only show bindings that use MOD (Super, Ctrl, Alt or Shift)
+      --focus-search=false
+                        Leave keyboard focus off the search bar
+
+PDF options:
+  -o, --output FILE     Output file (default: dwm-cheatsheet.pdf)
+      --paper SIZE      a4 or letter (default: a4)
+      --landscape       Use landscape orientation
+      --columns N       Number of card columns (default: 2 portrait, 3 landscape)
+
+Wallpaper options:
+      --size WxH              Screen resolution, repeat for several monitors (default: 1920x1080)
+  -o, --output FILE           Output file, suffixed with the size when several are given
+                              (default: dwm-cheatsheet-wallpaper.png)
+      --format FORMAT         png or svg (default: from the output extension)
+      --background COLOR      Background colour as #rrggbb
+      --background-image FILE PNG image scaled to cover the screen
+      --position POS          center, top, bottom, left, right, top-left, top-right,
+                              bottom-left or bottom-right (default: center)
+      --opacity VALUE         Opacity of the cards, 0.0 to 1.0 (default: 0.9)
+
+dmenu options:
+      --exec            Read the selected line from stdin and run or copy its binding,
+                        e.g. dwm-cheatsheet dmenu | dmenu -l 20 | dwm-cheatsheet dmenu --exec
+
+diff options:
+      --format FORMAT   text, json or markdown (default: text)
+      --gui             Show the comparison side by side in the cheatsheet window of NEW
+
+send-keys options:
+      --delay MS        Wait before sending, e.g. for the cheatsheet window to close (default: 0)
+
+  -h, --help            Show this help";
+
+pub struct Cli {
+    pub config: Option<PathBuf>,
+    pub command: Command,
+}
+
+pub enum Command {
+    Gui(WindowOptions),
+    Daemon(WindowOptions),
+    Toggle(WindowOptions),
+    Show(WindowOptions),
+    Pdf(PdfOptions),
+    Wallpaper(WallpaperOptions),
+    Dmenu { exec: bool },
+    Rofi,
+    SendKeys { combo: String, delay: Duration },
+    RunInDwm { combo: String },
+    Edit { combo: String },
+    State,
+    Deps,
+    Diff(DiffOptions),
+    Help,
+}
+
+/// Parses the process arguments (without the program name).
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Cli, String> {
+    let mut args = args.into_iter().peekable();
+    let mut config = None;
+
+    while args.peek().is_some_and(|arg| arg == "--config") {
+        let flag = args.next().expect("peeked");
+        config = Some(PathBuf::from(value(&flag, args.next())?));
+    }
+
+    let command = match args.next().as_deref() {
+        None => Command::Gui(WindowOptions::default()),
+        Some("-h" | "--help") => Command::Help,
+        Some(flag) if flag.starts_with("--") => {
+            let args = std::iter::once(flag.to_string()).chain(args);
+            parse_window(args, Command::Gui)?
+        }
+        Some("daemon") => parse_window(args, Command::Daemon)?,
+        Some("toggle") => parse_window(args, Command::Toggle)?,
+        Some("show") => parse_window(args, Command::Show)?,
+        Some("pdf") => parse_pdf(args)?,
+        Some("wallpaper") => parse_wallpaper(args)?,
+        Some("dmenu") => parse_dmenu(args)?,
+        Some("rofi") => Command::Rofi,
+        Some("send-keys") => parse_send_keys(args)?,
+        Some("run-command") => parse_combo(args, "run-command", |combo| Command::RunInDwm { combo })?,
+        Some("edit") => parse_combo(args, "edit", |combo| Command::Edit { combo })?,
+        Some("state") => Command::State,
+        Some("deps") => Command::Deps,
+        Some("diff") => parse_diff(args)?,
+        Some(other) => return Err(format!("unknown command '{}'", other)),
+    };
+
+    Ok(Cli { config, command })
+}
+
+fn parse_pdf(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut options = PdfOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => options.output = PathBuf::from(value(&arg, args.next())?),
+            "--paper" => {
+                options.paper = match value(&arg, args.next())?.to_lowercase().as_str() {
+                    "a4" => Paper::A4,
+                    "letter" => Paper::Letter,
+                    other => return Err(format!("unknown paper size '{}'", other)),
+                }
+            }
+            "--landscape" => options.landscape = true,
+            "--portrait" => options.landscape = false,
+            "--columns" => options.columns = Some(number(&arg, args.next())?),
+            "-h" | "--help" => return Ok(Command::Help),
+            other => return Err(format!("unknown option '{}'", other)),
+        }
+    }
+
+    Ok(Command::Pdf(options))
+}
+
+fn parse_wallpaper(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut options = WallpaperOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => options.sizes.push(size(&arg, args.next())?),
+            "-o" | "--output" => options.output = PathBuf::from(value(&arg, args.next())?),
+            "--format" => {
+                options.format = match value(&arg, args.next())?.to_lowercase().as_str() {
+                    "png" => Some(Format::Png),
+                    "svg" => Some(Format::Svg),
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            }
+            "--background" => {
+                let color = value(&arg, args.next())?;
+                options.background = Background::Color(
+                    wallpaper::parse_color(&color)
+                        .ok_or_else(|| format!("invalid colour '{}', expected #rrggbb", color))?,
+                );
+            }
+            "--background-image" => {
+                options.background = Background::Image(PathBuf::from(value(&arg, args.next())?))
+            }
+            "--position" => {
+                let position = value(&arg, args.next())?;
+                options.position = Position::parse(&position)
+                    .ok_or_else(|| format!("unknown position '{}'", position))?;
+            }
+            "--opacity" => {
+                let opacity = value(&arg, args.next())?;
+                options.opacity = match opacity.parse::<f32>() {
+                    Ok(o) if (0.0..=1.0).contains(&o) => o,
+                    _ => return Err(format!("--opacity expects 0.0 to 1.0, got '{}'", opacity)),
+                };
+            }
+            "-h" | "--help" => return Ok(Command::Help),
+            other => return Err(format!("unknown option '{}'", other)),
+        }
+    }
+
+    Ok(Command::Wallpaper(options))
+}
+
+fn parse_dmenu(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut exec = false;
+    for arg in args {
+        match arg.as_str() {
+            "--exec" => exec = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            other => return Err(format!("unknown option '{}'", other)),
+        }
+    }
+    Ok(Command::Dmenu { exec })
+}
+
+fn parse_window(
+    mut args: impl Iterator<Item = String>,
+    command: fn(WindowOptions) -> Command,
+) -> Result<Command, String> {
+    let mut options = WindowOptions::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            other if window_option(other, &mut args, &mut options)? => {}
+            other => return Err(format!("unknown option '{}'", other)),
+        }
+    }
+    Ok(command(options))
+}
+
+/// Applies `arg` if it is a window option; returns whether it was one.
+fn window_option(
+    arg: &str,
+    args: &mut impl Iterator<Item = String>,
+    options: &mut WindowOptions,
+) -> Result<bool, String> {
+    match arg {
+        "--overlay" => options.overlay = true,
+        "--reset-state" => options.reset_state = true,
+        "--search" => options.view.search = Some(value(arg, args.next())?),
+        "--category" => {
+            let name = value(arg, args.next())?;
+            let category = Category::from_name(&name).ok_or_else(|| {
+                let names: Vec<&str> = Category::ALL.iter().map(Category::name).collect();
+                format!("--category expects one of {}, got '{}'", names.join(", "), name)
+            })?;
+            if !options.view.categories.contains(&category) {
+                options.view.categories.push(category);
+            }
+        }
+        "--modifier" => {
+            let modifier = value(arg, args.next())?;
+            options.view.modifier = Some(
+                ["Super", "Ctrl", "Alt", "Shift"]
+                    .into_iter()
+                    .find(|m| m.eq_ignore_ascii_case(&modifier))
+                    .ok_or_else(|| {
+                        format!("--modifier expects Super, Ctrl, Alt or Shift, got '{}'", modifier)
+                    })?
+                    .to_string(),
+            );
+        }
+        "--focus-search" | "--focus-search=true" => options.focus_search = Some(true),
+        "--focus-search=false" => options.focus_search = Some(false),
+        "--class" => options.class = Some(value(arg, args.next())?),
+        "--name" => options.name = Some(value(arg, args.next())?),
+        "--geometry" => {
+            let geometry = value(arg, args.next())?;
+            options.geometry = Some(
+                WindowGeometry::parse(&geometry)
+                    .ok_or_else(|| format!("--geometry expects WxH+X+Y, got '{}'", geometry))?,
+            );
+        }
+        "--monitor" => {
+            let monitor = value(arg, args.next())?;
+            options.monitor = Some(
+                monitor
+                    .parse()
+                    .map_err(|_| format!("--monitor expects a number, got '{}'", monitor))?,
+            );
+        }
+        "--timeout" => {
+            let seconds = value(arg, args.next())?;
+            // `inf` and `1e40` parse but don't fit a Duration
+            let timeout = seconds
+                .parse::<f32>()
+                .ok()
+                .filter(|s| *s > 0.0)
+                .and_then(|s| Duration::try_from_secs_f32(s).ok());
+            options.timeout = Some(timeout.ok_or_else(|| format!("--timeout expects seconds, got '{}'", seconds))?);
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn parse_send_keys(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut combo = None;
+    let mut delay = Duration::ZERO;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--delay" => {
+                let millis = value(&arg, args.next())?;
+                delay = Duration::from_millis(
+                    millis
+                        .parse()
+                        .map_err(|_| format!("--delay expects milliseconds, got '{}'", millis))?,
+                );
+            }
+            "-h" | "--help" => return Ok(Command::Help),
+            other if other.starts_with("--") => return Err(format!("unknown option '{}'", other)),
+            _ if combo.is_some() => return Err("send-keys takes a single combo".to_string()),
+            _ => combo = Some(arg),
+        }
+    }
+
+    let combo = combo.ok_or("send-keys needs a combo such as Super+Shift+f")?;
+    Ok(Command::SendKeys { combo, delay })
+}
+
+/// Commands taking nothing but a combo, like `run-command` and `edit`.
+fn parse_combo(
+    args: impl Iterator<Item = String>,
+    name: &str,
+    command: impl FnOnce(String) -> Command,
+) -> Result<Command, String> {
+    let mut combo = None;
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            other if other.starts_with("--") => return Err(format!("unknown option '{}'", other)),
+            _ if combo.is_some() => return Err(format!("{} takes a single combo", name)),
+            _ => combo = Some(arg),
+        }
+    }
+
+    let combo = combo.ok_or_else(|| format!("{} needs a combo such as Super+Shift+f", name))?;
+    Ok(command(combo))
+}
+
+fn parse_diff(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut configs = Vec::new();
+    let mut format = compare::Format::Text;
+    let mut gui = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match value(&arg, args.next())?.to_lowercase().as_str() {
+                    "text" => compare::Format::Text,
+                    "json" => compare::Format::Json,
+                    "markdown" | "md" => compare::Format::Markdown,
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            }
+            "--gui" => gui = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            other if other.starts_with("--") => return Err(format!("unknown option '{}'", other)),
+            _ => configs.push(arg),
+        }
+    }
+
+    let [old, new]: [String; 2] = configs
+        .try_into()
+        .map_err(|_| "diff needs two configs, e.g. config.def.h config.h".to_string())?;
+    Ok(Command::Diff(DiffOptions { old, new, format, gui }))
+}
+
+fn value(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{} needs a value", flag))
+}
+
+fn number(flag: &str, value: Option<String>) -> Result<usize, String> {
+    let value = self::value(flag, value)?;
+    match value.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!("{} expects a positive number, got '{}'", flag, value)),
+    }
+}
+
+fn size(flag: &str, value: Option<String>) -> Result<(u32, u32), String> {
+    let value = self::value(flag, value)?;
+    let parsed = value
+        .split_once(['x', 'X'])
+        .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)));
+    match parsed {
+        Some((w, h)) if w > 0 && h > 0 => Ok((w, h)),
+        _ => Err(format!("{} expects WIDTHxHEIGHT, got '{}'", flag, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeout(seconds: &str) -> Result<Option<Duration>, String> {
+        let args = ["--overlay", "--timeout", seconds].map(String::from);
+        match parse(args)?.command {
+            Command::Gui(options) => Ok(options.timeout),
+            _ => panic!("expected the window command"),
+        }
+    }
+
+    #[test]
+    fn timeout_takes_positive_seconds() {
+        assert_eq!(timeout("2.5"), Ok(Some(Duration::from_millis(2500))));
+        for seconds in ["0", "-1", "soon", "NaN", "inf", "1e40"] {
+            assert_eq!(timeout(seconds), Err(format!("--timeout expects seconds, got '{}'", seconds)));
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use crate::config::{self, DwmConfig, Setting};
+use crate::keybindings::{self, Keybinding};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+
+/// How one binding or setting differs between two configs.
+#[derive(Debug, Clone)]
+pub enum Change<T> {
+    /// Only in the newer config.
+    Added(T),
+    /// Only in the older config.
+    Removed(T),
+    /// A combo calling another function or argument, or a setting with another value.
+    Changed { old: T, new: T },
+}
+
+impl<T> Change<T> {
+    /// The word used for the change in JSON and Markdown.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Change::Added(_) => "added",
+            Change::Removed(_) => "removed",
+            Change::Changed { .. } => "changed",
+        }
+    }
+
+    /// `+`, `−` or `~` in the text output and the GUI.
+    pub fn mark(&self) -> &'static str {
+        match self {
+            Change::Added(_) => "+",
+            Change::Removed(_) => "−",
+            Change::Changed { .. } => "~",
+        }
+    }
+
+    /// The older and newer side, either missing for added and removed ones.
+    pub fn sides(&self) -> (Option<&T>, Option<&T>) {
+        match self {
+            Change::Added(new) => (None, Some(new)),
+            Change::Removed(old) => (Some(old), None),
+            Change::Changed { old, new } => (Some(old), Some(new)),
+        }
+    }
+}
+
+/// What differs between two configs, e.g. a `config.h` and the `config.def.h`
+/// it was copied from.
+#[derive(Debug, Clone, Default)]
+pub struct Comparison {
+    /// In the newer config's order, followed by the removed ones.
+    pub bindings: Vec<Change<Keybinding>>,
+    pub settings: Vec<Change<Setting>>,
+}
+
+impl Comparison {
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty() && self.settings.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bindings.len() + self.settings.len()
+    }
+}
+
+/// Two configs compared, with the names to show them by.
+pub struct Diff {
+    /// The older side, e.g. `config.def.h` or `git:HEAD~1:config.h`.
+    pub old: String,
+    pub new: String,
+    pub comparison: Comparison,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Markdown,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    /// A path, or `git:<rev>:<path>`.
+    pub old: String,
+    pub new: String,
+    pub format: Format,
+    /// Show the comparison in the cheatsheet window of the newer config.
+    pub gui: bool,
+}
+
+/// `config` compared with the other file of its `config.h` / `config.def.h`
+/// pair; `None` for the bundled config or when there's no pair.
+pub fn against_counterpart(config: &DwmConfig) -> Option<Diff> {
+    let path = config.path.as_deref()?;
+    let other = config::load(Some(&config::counterpart(path)?)).ok()?;
+    let (defaults, built) = if path.ends_with("config.h") { (&other, config) } else { (config, &other) };
+    let name = |config: &DwmConfig| config.path.as_deref().map(file_name).unwrap_or_default();
+    Some(Diff {
+        old: name(defaults),
+        new: name(built),
+        comparison: compare(defaults, built),
+    })
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into_owned())
+}
+
+/// Reads a config from a file or directory as [`config::load`] does, or from a
+/// git revision written `git:<rev>:<path>`, through `git show` in the current
+/// directory.
+pub fn read(spec: &str) -> io::Result<DwmConfig> {
+    let Some(object) = spec.strip_prefix("git:") else {
+        return config::load(Some(Path::new(spec)));
+    };
+    if !object.contains(':') {
+        let message = format!("{}: expected git:<rev>:<path>", spec);
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+    }
+    let output = Command::new("git")
+        .args(["show", object])
+        .output()
+        .map_err(|err| io::Error::new(err.kind(), format!("git: {}", err)))?;
+    if !output.status.success() {
+        let message = format!("{}: {}", spec, String::from_utf8_lossy(&output.stderr).trim());
+        return Err(io::Error::new(io::ErrorKind::NotFound, message));
+    }
+    Ok(config::parse(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Compares the configs `options` names.
+pub fn diff(options: &DiffOptions) -> io::Result<Diff> {
+    let old = read(&options.old)?;
+    let new = read(&options.new)?;
+    Ok(Diff {
+        old: options.old.clone(),
+        new: options.new.clone(),
+        comparison: compare(&old, &new),
+    })
+}
+
+/// Bindings of the same modifiers and key, ignoring modifier order and letter
+/// case, count as the same one; a combo bound twice is matched up in file order.
+pub fn compare(old: &DwmConfig, new: &DwmConfig) -> Comparison {
+    let old_bindings = keybindings::config_keybindings(old);
+    let new_bindings = keybindings::config_keybindings(new);
+    let mut unmatched: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, keybinding) in old_bindings.iter().enumerate() {
+        unmatched.entry(identity(keybinding)).or_default().push(i);
+    }
+    let mut matched = vec![false; old_bindings.len()];
+
+    let mut bindings = Vec::new();
+    for keybinding in new_bindings {
+        let same = unmatched
+            .get_mut(&identity(&keybinding))
+            .filter(|indices| !indices.is_empty())
+            .map(|indices| indices.remove(0));
+        let Some(i) = same else {
+            bindings.push(Change::Added(keybinding));
+            continue;
+        };
+        matched[i] = true;
+        let old = &old_bindings[i];
+        let call = |kb: &Keybinding| kb.action.as_ref().map(|action| (action.function.clone(), action.argument.clone()));
+        if call(old) != call(&keybinding) {
+            bindings.push(Change::Changed {
+                old: old.clone(),
+                new: keybinding,
+            });
+        }
+    }
+    bindings.extend(
+        old_bindings
+            .into_iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(keybinding, _)| Change::Removed(keybinding)),
+    );
+
+    let mut settings = Vec::new();
+    for setting in &new.settings {
+        match old.settings.iter().find(|other| other.name == setting.name) {
+            None => settings.push(Change::Added(setting.clone())),
+            Some(old) if old.value != setting.value => settings.push(Change::Changed {
+                old: old.clone(),
+                new: setting.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    settings.extend(
+        old.settings
+            .iter()
+            .filter(|setting| !new.settings.iter().any(|other| other.name == setting.name))
+            .map(|setting| Change::Removed(setting.clone())),
+    );
+
+    Comparison { bindings, settings }
+}
+
+/// The combo with its modifiers sorted and everything lowercased, so
+/// `Shift+Super+Q` and `Super+Shift+q` are the same binding.
+fn identity(keybinding: &Keybinding) -> String {
+    let mut parts: Vec<String> = keybinding.modifiers.iter().map(|m| m.to_lowercase()).collect();
+    parts.sort();
+    parts.push(keybinding.key.to_lowercase());
+    parts.join("+")
+}
+
+/// What a binding does, as the text output and the GUI show it, e.g.
+/// `Run alacritty (spawn .v = alacritty)`.
+pub fn binding_text(keybinding: &Keybinding) -> String {
+    match &keybinding.action {
+        Some(action) => {
+            let call = format!("{} {}", action.function, action.argument);
+            format!("{} ({})", keybinding.description, call.trim_end())
+        }
+        None => keybinding.description.clone(),
+    }
+}
+
+/// Prints `diff` in `format`.
+pub fn print(diff: &Diff, format: Format) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    match format {
+        Format::Text => print_text(&mut stdout, diff),
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut stdout, &to_json(diff))?;
+            writeln!(stdout)
+        }
+        Format::Markdown => print_markdown(&mut stdout, diff),
+    }
+}
+
+fn print_text(out: &mut impl Write, diff: &Diff) -> io::Result<()> {
+    writeln!(out, "--- {}", diff.old)?;
+    writeln!(out, "+++ {}", diff.new)?;
+    if diff.comparison.is_empty() {
+        return writeln!(out, "No differences");
+    }
+    if !diff.comparison.bindings.is_empty() {
+        writeln!(out, "Bindings")?;
+    }
+    for change in &diff.comparison.bindings {
+        let (old, new) = change.sides();
+        let text = match (old, new) {
+            (Some(old), Some(new)) => format!("{}  →  {}", binding_text(old), binding_text(new)),
+            _ => old.or(new).map(binding_text).unwrap_or_default(),
+        };
+        let combo = new.or(old).map(Keybinding::combo).unwrap_or_default();
+        writeln!(out, "  {} {:<24} {}", change.mark(), combo, text)?;
+    }
+    if !diff.comparison.settings.is_empty() {
+        writeln!(out, "Settings")?;
+    }
+    for change in &diff.comparison.settings {
+        let (old, new) = change.sides();
+        let text = match (old, new) {
+            (Some(old), Some(new)) => format!("{}  →  {}", old.value, new.value),
+            _ => old.or(new).map(|setting| setting.value.clone()).unwrap_or_default(),
+        };
+        let name = new.or(old).map(|setting| setting.name.as_str()).unwrap_or_default();
+        writeln!(out, "  {} {:<24} {}", change.mark(), name, text)?;
+    }
+    Ok(())
+}
+
+fn to_json(diff: &Diff) -> Value {
+    let binding = |keybinding: Option<&Keybinding>| {
+        keybinding.map_or(Value::Null, |kb| {
+            let action = kb.action.as_ref();
+            json!({
+                "function": action.map(|action| action.function.clone()),
+                "argument": action.map(|action| action.argument.to_string()),
+                "description": kb.description,
+                "line": action.map(|action| action.line),
+            })
+        })
+    };
+    let bindings: Vec<Value> = diff
+        .comparison
+        .bindings
+        .iter()
+        .map(|change| {
+            let (old, new) = change.sides();
+            json!({
+                "change": change.kind(),
+                "combo": new.or(old).map(Keybinding::combo),
+                "old": binding(old),
+                "new": binding(new),
+            })
+        })
+        .collect();
+    let settings: Vec<Value> = diff
+        .comparison
+        .settings
+        .iter()
+        .map(|change| {
+            let (old, new) = change.sides();
+            json!({
+                "change": change.kind(),
+                "name": new.or(old).map(|setting| setting.name.clone()),
+                "old": old.map(|setting| setting.value.clone()),
+                "new": new.map(|setting| setting.value.clone()),
+            })
+        })
+        .collect();
+    json!({
+        "old": diff.old,
+        "new": diff.new,
+        "bindings": bindings,
+        "settings": settings,
+    })
+}
+
+fn print_markdown(out: &mut impl Write, diff: &Diff) -> io::Result<()> {
+    writeln!(out, "# `{}` → `{}`", diff.old, diff.new)?;
+    if diff.comparison.is_empty() {
+        writeln!(out)?;
+        return writeln!(out, "No differences.");
+    }
+    let cell = |text: &str| text.replace('|', "\\|");
+    if !diff.comparison.bindings.is_empty() {
+        writeln!(out, "\n## Bindings\n")?;
+        writeln!(out, "| Change | Combo | `{}` | `{}` |", cell(&diff.old), cell(&diff.new))?;
+        writeln!(out, "|---|---|---|---|")?;
+        for change in &diff.comparison.bindings {
+            let (old, new) = change.sides();
+            let side = |kb: Option<&Keybinding>| kb.map(|kb| cell(&binding_text(kb))).unwrap_or_default();
+            let combo = new.or(old).map(Keybinding::combo).unwrap_or_default();
+            writeln!(out, "| {} | `{}` | {} | {} |", change.kind(), combo, side(old), side(new))?;
+        }
+    }
+    if !diff.comparison.settings.is_empty() {
+        writeln!(out, "\n## Settings\n")?;
+        writeln!(out, "| Change | Setting | `{}` | `{}` |", cell(&diff.old), cell(&diff.new))?;
+        writeln!(out, "|---|---|---|---|")?;
+        for change in &diff.comparison.settings {
+            let (old, new) = change.sides();
+            let side = |setting: Option<&Setting>| setting.map(|s| format!("`{}`", cell(&s.value))).unwrap_or_default();
+            let name = new.or(old).map(|setting| setting.name.as_str()).unwrap_or_default();
+            writeln!(out, "| {} | `{}` | {} | {} |", change.kind(), name, side(old), side(new))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,1161 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Shipped with the cheatsheet so actions are available without a local dwm checkout.
+const DEFAULT_CONFIG: &str = include_str!("../config.def.h");
+
+/// The parts of a dwm `config.h` the cheatsheet understands.
+#[derive(Debug, Clone, Default)]
+pub struct DwmConfig {
+    /// File the config was read from, `None` for the built-in default.
+    pub path: Option<PathBuf>,
+    pub keys: Vec<Key>,
+    /// Key initializers in comments and preprocessor branches that aren't compiled, which dwm never sees.
+    pub disabled_keys: Vec<Key>,
+    pub layouts: Vec<Layout>,
+    /// `ipcsockpath` from the dwm-ipc patch, if the config sets it.
+    pub ipc_socket: Option<String>,
+    /// Top-level scalars and string arrays, like `borderpx` or `tags[]`, in file order.
+    pub settings: Vec<Setting>,
+}
+
+impl DwmConfig {
+    /// The `keys[]` entry for a combo such as `Super+Shift+f`, ignoring modifier
+    /// order and letter case.
+    pub fn find_key(&self, combo: &str) -> Option<&Key> {
+        let mut modifiers: Vec<&str> = combo.split('+').map(str::trim).collect();
+        let keysym = modifiers.pop()?;
+        self.keys.iter().find(|key| {
+            key.keysym.eq_ignore_ascii_case(keysym)
+                && key.modifiers.len() == modifiers.len()
+                && modifiers
+                    .iter()
+                    .all(|m| key.modifiers.iter().any(|o| o.eq_ignore_ascii_case(m)))
+        })
+    }
+}
+
+/// A top-level variable such as `static const unsigned int borderpx = 3;`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting {
+    pub name: String,
+    /// The value with macros expanded, e.g. `3` or `{"1", "2", "3"}`.
+    pub value: String,
+    pub line: usize,
+}
+
+/// One entry of the `layouts[]` array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    pub symbol: String,
+    /// The arrange function, `None` for the floating layout.
+    pub arrange: Option<String>,
+}
+
+/// One entry of the `keys[]` array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Key {
+    pub modifiers: Vec<String>,
+    /// Keysym without its `XK_` prefix, e.g. `Return` or `XF86AudioMute`.
+    pub keysym: String,
+    pub action: Action,
+}
+
+/// The function a binding calls and the argument it passes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Action {
+    pub function: String,
+    pub argument: Argument,
+    /// 1-based line of the initializer in the config file.
+    pub line: usize,
+    /// Line of its closing brace, past `line` when it spans several.
+    #[serde(default)]
+    pub end_line: usize,
+    /// The initializer as written, e.g. `{MODKEY | ShiftMask, XK_j, movestack, {.i = +1}}`;
+    /// the macro call for keys from one like `TAGKEYS`.
+    #[serde(default)]
+    pub source: String,
+    /// Config file the binding comes from, `None` for the bundled default.
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+    /// Commented out or in a preprocessor branch that isn't compiled, like `#if 0`.
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// dwm's `Arg` union, with `.v` resolved where the config allows it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Argument {
+    None,
+    Int(i32),
+    UInt(u32),
+    Float(f32),
+    /// The argv passed to `spawn`.
+    Command(Vec<String>),
+    /// Index into `layouts[]`.
+    Layout(usize),
+    /// An identifier the parser could not resolve, e.g. `default_border`.
+    Symbol(String),
+}
+
+/// The argument roughly as written in a `Key` initializer, e.g. `.i = -3`; empty
+/// for `{0}` and the bare expression when it could not be resolved.
+impl fmt::Display for Argument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Argument::None => Ok(()),
+            Argument::Int(n) => write!(f, ".i = {}", n),
+            Argument::UInt(n) => write!(f, ".ui = {}", n),
+            Argument::Float(n) => write!(f, ".f = {}", n),
+            Argument::Command(argv) => write!(
+                f,
+                ".v = {}",
+                argv.iter().map(|arg| shell_quote(arg)).collect::<Vec<_>>().join(" ")
+            ),
+            Argument::Layout(index) => write!(f, ".v = &layouts[{}]", index),
+            Argument::Symbol(text) => f.write_str(text),
+        }
+    }
+}
+
+impl Action {
+    /// The argv this action spawns, if it is a `spawn` binding.
+    pub fn command(&self) -> Option<&[String]> {
+        match (&self.function[..], &self.argument) {
+            ("spawn", Argument::Command(argv)) => Some(argv),
+            _ => None,
+        }
+    }
+
+    /// The spawned command as a shell line: the `SHCMD` string itself, or the
+    /// argv joined with quoting where needed.
+    pub fn shell_command(&self) -> Option<String> {
+        let argv = self.command()?;
+        match argv {
+            [sh, flag, cmd] if sh == "/bin/sh" && flag == "-c" => Some(cmd.clone()),
+            _ => Some(
+                argv.iter()
+                    .map(|arg| shell_quote(arg))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+        }
+    }
+}
+
+fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=+%:,@~".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Config files tried, in order, when no path is given on the command line.
+fn candidates() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"));
+    vec![
+        config_home.join("chadwm/chadwm/config.h"),
+        config_home.join("chadwm/chadwm/config.def.h"),
+        config_home.join("dwm/config.h"),
+        config_home.join("dwm/config.def.h"),
+    ]
+}
+
+/// `config.h` in `dir`, or `config.def.h` when it hasn't been copied yet.
+fn in_dir(dir: &Path) -> Option<PathBuf> {
+    ["config.h", "config.def.h"].iter().map(|name| dir.join(name)).find(|path| path.is_file())
+}
+
+/// The other file of a `config.h` / `config.def.h` pair next to `path`, if it
+/// exists: what dwm builds from and the defaults it was copied from.
+pub fn counterpart(path: &Path) -> Option<PathBuf> {
+    let other = match path.file_name()?.to_str()? {
+        "config.h" => "config.def.h",
+        "config.def.h" => "config.h",
+        _ => return None,
+    };
+    Some(path.with_file_name(other)).filter(|other| other.is_file())
+}
+
+/// The config file `--config` names: `path` itself, or for a directory its
+/// `config.h` or else `config.def.h`.
+pub fn resolve(path: &Path) -> io::Result<PathBuf> {
+    if !path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+    in_dir(path).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, format!("{}: no config.h or config.def.h", path.display()))
+    })
+}
+
+/// Loads `path` (for a directory, its `config.h` or else `config.def.h`), or the
+/// first config found in the usual places, falling back to the built-in default.
+pub fn load(path: Option<&Path>) -> io::Result<DwmConfig> {
+    let path = match path {
+        Some(path) => Some(resolve(path)?),
+        None => candidates().into_iter().find(|path| path.is_file()),
+    };
+
+    match path {
+        Some(path) => {
+            let source = fs::read_to_string(&path)
+                .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+            let mut config = parse(&source);
+            for key in config.keys.iter_mut().chain(&mut config.disabled_keys) {
+                key.action.file = Some(path.clone());
+            }
+            config.path = Some(path);
+            Ok(config)
+        }
+        None => Ok(parse(DEFAULT_CONFIG)),
+    }
+}
+
+/// Parses config source. Anything that isn't understood is skipped.
+pub fn parse(source: &str) -> DwmConfig {
+    let (tokens, macros, disabled) = preprocess(source);
+    let tokens = expand(&tokens, &macros, 0);
+
+    let mut commands: HashMap<String, Vec<String>> = HashMap::new();
+    let mut key_lists = Vec::new();
+    let mut layouts = Vec::new();
+    let mut ipc_socket = None;
+    let mut settings = Vec::new();
+    let mut i = 0;
+    while i + 4 < tokens.len() {
+        // TYPE NAME = ... ;  or  TYPE *NAME = ... ;
+        let declared = i > 0 && (matches!(tokens[i - 1].tok, Tok::Ident(_)) || tokens[i - 1].is("*"));
+        if let Tok::Ident(name) = &tokens[i].tok {
+            if declared && tokens[i + 1].is("=") && !tokens[i + 2].is("{") {
+                let end = tokens[i..].iter().position(|t| t.is(";")).map_or(tokens.len(), |n| i + n);
+                let value = &tokens[i + 2..end];
+                if name == "ipcsockpath" {
+                    ipc_socket = string_literal(value);
+                }
+                settings.push(Setting {
+                    name: name.clone(),
+                    value: expr_text(value),
+                    line: tokens[i].line,
+                });
+                i = end;
+                continue;
+            }
+        }
+
+        // NAME [ ] = {
+        let is_array = matches!(&tokens[i].tok, Tok::Ident(_))
+            && tokens[i + 1].is("[")
+            && tokens[i + 2].is("]")
+            && tokens[i + 3].is("=")
+            && tokens[i + 4].is("{");
+        if !is_array {
+            i += 1;
+            continue;
+        }
+        let Tok::Ident(name) = &tokens[i].tok else {
+            unreachable!()
+        };
+        let (items, end) = parse_list(&tokens, i + 5);
+        if name == "keys" {
+            key_lists.push(items);
+        } else if name == "layouts" {
+            layouts = items.iter().filter_map(parse_layout).collect();
+        } else if let Some(argv) = string_array(&items) {
+            let quoted: Vec<String> = argv.iter().map(|arg| format!("{:?}", arg)).collect();
+            settings.push(Setting {
+                name: format!("{}[]", name),
+                value: format!("{{{}}}", quoted.join(", ")),
+                line: tokens[i].line,
+            });
+            commands.insert(name.clone(), argv);
+        }
+        i = end;
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let keys = key_lists
+        .iter()
+        .flatten()
+        .filter_map(|item| parse_key(item, &commands, &lines))
+        .collect();
+    let disabled_keys = disabled
+        .iter()
+        .flat_map(|fragment| disabled_keys(&expand(fragment, &macros, 0), &commands, &lines))
+        .collect();
+
+    DwmConfig {
+        path: None,
+        keys,
+        disabled_keys,
+        layouts,
+        ipc_socket,
+        settings,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Number(String),
+    Str(String),
+    Punct(String),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    line: usize,
+}
+
+impl Token {
+    fn is(&self, punct: &str) -> bool {
+        matches!(&self.tok, Tok::Punct(p) if p == punct)
+    }
+}
+
+struct Macro {
+    params: Option<Vec<String>>,
+    body: Vec<Token>,
+}
+
+/// Removes comments (keeping line breaks), collects `#define`s and tokenizes the rest.
+/// Comments and branches of `#if`s that aren't compiled come back tokenized on their own, one list per
+/// run of adjacent lines, for [`disabled_keys`].
+fn preprocess(source: &str) -> (Vec<Token>, HashMap<String, Macro>, Vec<Vec<Token>>) {
+    let (stripped, comments) = strip_comments(source);
+    let mut tokens = Vec::new();
+    let mut macros = HashMap::new();
+    let mut disabled: Vec<Vec<Token>> = comments
+        .iter()
+        .map(|fragment| fragment.iter().flat_map(|(line, text)| tokenize(text, *line)).collect())
+        .collect();
+    // Open conditionals, innermost last, with the disabled tokens read so far
+    let mut conditionals: Vec<Conditional> = Vec::new();
+    let mut block = Vec::new();
+
+    let mut lines = stripped.lines().enumerate().peekable();
+    while let Some((index, line)) = lines.next() {
+        let line_no = index + 1;
+        let mut logical = line.to_string();
+        while logical.ends_with('\\') {
+            logical.pop();
+            match lines.next() {
+                Some((_, next)) => logical.push_str(next),
+                None => break,
+            }
+        }
+        let live = conditionals.last().is_none_or(|conditional| conditional.live);
+
+        if let Some(directive) = logical.trim_start().strip_prefix('#') {
+            let directive = directive.trim_start();
+            let split = directive.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(directive.len());
+            let (name, rest) = directive.split_at(split);
+            if !block.is_empty() {
+                disabled.push(std::mem::take(&mut block));
+            }
+            match name {
+                "if" | "ifdef" | "ifndef" => {
+                    let value = condition(name, rest.trim(), &macros);
+                    conditionals.push(Conditional {
+                        outer_live: live,
+                        taken: value != Some(false),
+                        live: live && value != Some(false),
+                    });
+                }
+                "elif" | "else" => {
+                    if let Some(conditional) = conditionals.last_mut() {
+                        let value = if name == "else" { None } else { condition("if", rest.trim(), &macros) };
+                        let enters = !conditional.taken && value != Some(false);
+                        conditional.live = conditional.outer_live && enters;
+                        conditional.taken |= enters;
+                    }
+                }
+                "endif" => {
+                    conditionals.pop();
+                }
+                "define" if live => {
+                    if let Some((name, mac)) = parse_define(rest, line_no) {
+                        macros.insert(name, mac);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        if live {
+            tokens.extend(tokenize(&logical, line_no));
+        } else {
+            block.extend(tokenize(&logical, line_no));
+        }
+    }
+    if !block.is_empty() {
+        disabled.push(block);
+    }
+
+    (tokens, macros, disabled)
+}
+
+/// An open `#if`, `#ifdef` or `#ifndef` in [`preprocess`].
+struct Conditional {
+    /// Whether the code around the conditional is compiled.
+    outer_live: bool,
+    /// Whether a branch has been compiled, so later ones aren't.
+    taken: bool,
+    /// Whether the current branch is compiled.
+    live: bool,
+}
+
+/// The value of a conditional directive's test, `None` when it can't be told
+/// from the config alone: an expression other than a number, or a macro not
+/// defined in the config (it may come from a header). Such a branch is taken
+/// as compiled and the ones after it as not, since dwm only builds with one.
+fn condition(directive: &str, test: &str, macros: &HashMap<String, Macro>) -> Option<bool> {
+    match directive {
+        "ifdef" => macros.contains_key(test).then_some(true),
+        "ifndef" => macros.contains_key(test).then_some(false),
+        _ => test.parse::<i64>().ok().map(|n| n != 0),
+    }
+}
+
+/// Every `{mod, XK_key, function, arg}` initializer in disabled code, e.g.
+/// `// {MODKEY, XK_d, spawn, SHCMD("launcher")}`, marked disabled.
+fn disabled_keys(tokens: &[Token], commands: &HashMap<String, Vec<String>>, lines: &[&str]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i].is("{") {
+            let (items, end) = parse_list(tokens, i + 1);
+            let is_keysym = match items.get(1) {
+                Some(Item::Expr(keysym)) => matches!(
+                    &keysym[..],
+                    [Token { tok: Tok::Ident(name), .. }] if name.starts_with("XK_") || name.starts_with("XF86XK_")
+                ),
+                _ => false,
+            };
+            let item = Item::List(items, tokens[i].line, tokens[end.min(tokens.len()) - 1].line);
+            if let Some(mut key) = is_keysym.then(|| parse_key(&item, commands, lines)).flatten() {
+                key.action.disabled = true;
+                keys.push(key);
+                i = end;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    keys
+}
+
+/// Comment text as `(line, text)` pairs, adjacent comments merged into one run.
+type Comments = Vec<Vec<(usize, String)>>;
+
+/// Replaces comments with whitespace, keeping line breaks, and returns them too.
+fn strip_comments(source: &str) -> (String, Comments) {
+    let mut out = String::with_capacity(source.len());
+    let mut comments: Comments = Vec::new();
+    let mut line = 1;
+    let mut chars = source.chars().peekable();
+    // A comment continues the previous run when it starts on that run's last line or the next
+    let add_comment = |comments: &mut Comments, start: usize, text: &str| {
+        let lines = text.split('\n').enumerate().map(|(i, text)| (start + i, text.to_string()));
+        match comments.last_mut() {
+            Some(run) if run.last().is_some_and(|(last, _)| last + 1 >= start) => run.extend(lines),
+            _ => comments.push(lines.collect()),
+        }
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                out.push(c);
+                while let Some(s) = chars.next() {
+                    out.push(s);
+                    if s == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            out.push(escaped);
+                            if escaped == '\n' {
+                                line += 1;
+                            }
+                        }
+                    } else if s == c || s == '\n' {
+                        if s == '\n' {
+                            line += 1;
+                        }
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                chars.next();
+                let mut text = String::new();
+                for s in chars.by_ref() {
+                    if s == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                    text.push(s);
+                }
+                add_comment(&mut comments, line, &text);
+                line += 1;
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let start = line;
+                let mut text = String::new();
+                let mut previous = ' ';
+                for s in chars.by_ref() {
+                    if s == '\n' {
+                        out.push('\n');
+                        line += 1;
+                    }
+                    if previous == '*' && s == '/' {
+                        text.pop();
+                        break;
+                    }
+                    text.push(s);
+                    previous = s;
+                }
+                add_comment(&mut comments, start, &text);
+                out.push(' ');
+            }
+            '\n' => {
+                out.push(c);
+                line += 1;
+            }
+            _ => out.push(c),
+        }
+    }
+    (out, comments)
+}
+
+fn parse_define(define: &str, line: usize) -> Option<(String, Macro)> {
+    let define = define.trim_start();
+    let name_len = define
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(define.len());
+    let (name, rest) = define.split_at(name_len);
+    if name.is_empty() {
+        return None;
+    }
+
+    // A parenthesis directly after the name makes it function-like
+    let (params, body) = match rest.strip_prefix('(') {
+        Some(rest) => {
+            let (params, body) = rest.split_once(')')?;
+            let params = params
+                .split(',')
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect();
+            (Some(params), body)
+        }
+        None => (None, rest),
+    };
+
+    Some((
+        name.to_string(),
+        Macro {
+            params,
+            body: tokenize(body, line),
+        },
+    ))
+}
+
+fn tokenize(text: &str, line: usize) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let tok = if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            Tok::Ident(chars[start..i].iter().collect())
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            Tok::Number(chars[start..i].iter().collect())
+        } else if c == '"' || c == '\'' {
+            i += 1;
+            let mut value = String::new();
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                    value.push(match chars[i] {
+                        'n' => '\n',
+                        't' => '\t',
+                        '0' => '\0',
+                        other => other,
+                    });
+                } else {
+                    value.push(chars[i]);
+                }
+                i += 1;
+            }
+            i += 1;
+            if c == '"' {
+                Tok::Str(value)
+            } else {
+                Tok::Number(value.chars().next().map_or(0, |c| c as u32).to_string())
+            }
+        } else if c == '<' && chars.get(i + 1) == Some(&'<') {
+            i += 2;
+            Tok::Punct("<<".to_string())
+        } else {
+            i += 1;
+            Tok::Punct(c.to_string())
+        };
+        tokens.push(Token { tok, line });
+    }
+    tokens
+}
+
+/// Expands macros the way the C preprocessor would for the subset dwm configs use
+/// (`MODKEY`, `TAGKEYS(KEY, TAG)`, `SHCMD(cmd)`). Expanded tokens take the line of
+/// the invocation.
+fn expand(tokens: &[Token], macros: &HashMap<String, Macro>, depth: usize) -> Vec<Token> {
+    if depth > 16 {
+        return tokens.to_vec();
+    }
+
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = &tokens[i];
+        let Tok::Ident(name) = &token.tok else {
+            out.push(token.clone());
+            i += 1;
+            continue;
+        };
+        let Some(mac) = macros.get(name) else {
+            out.push(token.clone());
+            i += 1;
+            continue;
+        };
+
+        let body = match &mac.params {
+            None => {
+                i += 1;
+                mac.body.clone()
+            }
+            Some(params) => {
+                if !tokens.get(i + 1).is_some_and(|t| t.is("(")) {
+                    out.push(token.clone());
+                    i += 1;
+                    continue;
+                }
+                let (args, end) = macro_args(tokens, i + 2);
+                i = end;
+                mac.body
+                    .iter()
+                    .flat_map(|t| match &t.tok {
+                        Tok::Ident(id) => match params.iter().position(|p| p == id) {
+                            Some(index) => args.get(index).cloned().unwrap_or_default(),
+                            None => vec![t.clone()],
+                        },
+                        _ => vec![t.clone()],
+                    })
+                    .collect()
+            }
+        };
+
+        let body: Vec<Token> = body
+            .into_iter()
+            .map(|t| Token {
+                tok: t.tok,
+                line: token.line,
+            })
+            .collect();
+        out.extend(expand(&body, macros, depth + 1));
+    }
+    out
+}
+
+/// Splits macro arguments starting after the opening parenthesis; returns them and
+/// the index after the closing parenthesis.
+fn macro_args(tokens: &[Token], mut i: usize) -> (Vec<Vec<Token>>, usize) {
+    let mut args = vec![Vec::new()];
+    let mut depth = 0;
+    while i < tokens.len() {
+        let token = &tokens[i];
+        i += 1;
+        match &token.tok {
+            Tok::Punct(p) if p == "(" || p == "{" || p == "[" => depth += 1,
+            Tok::Punct(p) if (p == ")" || p == "}" || p == "]") && depth > 0 => depth -= 1,
+            Tok::Punct(p) if p == ")" => break,
+            Tok::Punct(p) if p == "," && depth == 0 => {
+                args.push(Vec::new());
+                continue;
+            }
+            _ => {}
+        }
+        args.last_mut().expect("at least one argument").push(token.clone());
+    }
+    (args, i)
+}
+
+/// An element of a brace-enclosed initializer list.
+#[derive(Debug)]
+enum Item {
+    /// Items and the lines of the opening and closing brace.
+    List(Vec<Item>, usize, usize),
+    Expr(Vec<Token>),
+}
+
+/// Parses initializer items starting after an opening brace; returns them and the
+/// index after the matching closing brace.
+fn parse_list(tokens: &[Token], mut i: usize) -> (Vec<Item>, usize) {
+    let mut items = Vec::new();
+    while i < tokens.len() {
+        let token = &tokens[i];
+        if token.is("}") {
+            return (items, i + 1);
+        }
+        if token.is(",") {
+            i += 1;
+            continue;
+        }
+        if token.is("{") {
+            let (list, end) = parse_list(tokens, i + 1);
+            let end_line = tokens[end.min(tokens.len()) - 1].line;
+            items.push(Item::List(list, token.line, end_line));
+            i = end;
+            continue;
+        }
+
+        let mut expr = Vec::new();
+        let mut depth = 0;
+        while i < tokens.len() {
+            let token = &tokens[i];
+            if depth == 0 && (token.is(",") || token.is("}")) {
+                break;
+            }
+            if token.is("(") || token.is("{") || token.is("[") {
+                depth += 1;
+            } else if token.is(")") || token.is("}") || token.is("]") {
+                depth -= 1;
+            }
+            expr.push(token.clone());
+            i += 1;
+        }
+        items.push(Item::Expr(expr));
+    }
+    (items, i)
+}
+
+/// `{"cmd", "arg", NULL}` as an argv.
+fn string_array(items: &[Item]) -> Option<Vec<String>> {
+    let mut argv = Vec::new();
+    for item in items {
+        match item {
+            Item::Expr(tokens) => match &tokens[..] {
+                [Token { tok: Tok::Ident(null), .. }] if null == "NULL" => break,
+                _ => argv.push(string_literal(tokens)?),
+            },
+            Item::List(..) => return None,
+        }
+    }
+    (!argv.is_empty()).then_some(argv)
+}
+
+/// A string literal, including adjacent literals that C concatenates.
+fn string_literal(tokens: &[Token]) -> Option<String> {
+    let mut value = String::new();
+    for token in tokens {
+        match &token.tok {
+            Tok::Str(s) => value.push_str(s),
+            _ => return None,
+        }
+    }
+    (!tokens.is_empty()).then_some(value)
+}
+
+/// `{"[]=", tile}`; the `{NULL, NULL}` terminator is skipped.
+fn parse_layout(item: &Item) -> Option<Layout> {
+    let Item::List(fields, ..) = item else {
+        return None;
+    };
+    let [Item::Expr(symbol), Item::Expr(arrange)] = &fields[..] else {
+        return None;
+    };
+    let arrange = match &arrange[..] {
+        [Token { tok: Tok::Ident(name), .. }] if name == "NULL" => None,
+        [Token { tok: Tok::Ident(name), .. }] => Some(name.clone()),
+        _ => return None,
+    };
+    Some(Layout {
+        symbol: string_literal(symbol)?,
+        arrange,
+    })
+}
+
+fn parse_key(item: &Item, commands: &HashMap<String, Vec<String>>, lines: &[&str]) -> Option<Key> {
+    let Item::List(fields, line, end_line) = item else {
+        return None;
+    };
+    let [Item::Expr(modifiers), Item::Expr(keysym), Item::Expr(function), argument] = &fields[..]
+    else {
+        return None;
+    };
+    let [Token { tok: Tok::Ident(keysym), .. }] = &keysym[..] else {
+        return None;
+    };
+    let [Token { tok: Tok::Ident(function), .. }] = &function[..] else {
+        return None;
+    };
+
+    Some(Key {
+        modifiers: parse_modifiers(modifiers),
+        keysym: keysym_name(keysym),
+        action: Action {
+            function: function.clone(),
+            argument: parse_argument(argument, commands),
+            line: *line,
+            end_line: *end_line,
+            source: source_text(lines, *line, *end_line),
+            file: None,
+            disabled: false,
+        },
+    })
+}
+
+/// Lines `start..=end` as written, without their indentation and the comma
+/// after the initializer.
+fn source_text(lines: &[&str], start: usize, end: usize) -> String {
+    let text = lines
+        .get(start.saturating_sub(1)..end.min(lines.len()))
+        .unwrap_or_default()
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join("\n");
+    text.trim_end_matches(',').to_string()
+}
+
+/// Modifier names in the order the cheatsheet displays them.
+const MODIFIER_ORDER: [&str; 4] = ["Super", "Ctrl", "Alt", "Shift"];
+
+fn parse_modifiers(tokens: &[Token]) -> Vec<String> {
+    let mut modifiers: Vec<String> = tokens
+        .iter()
+        .filter_map(|token| match &token.tok {
+            Tok::Ident(mask) => Some(modifier_name(mask)),
+            _ => None,
+        })
+        .collect();
+    modifiers.sort_by_key(|m| {
+        MODIFIER_ORDER
+            .iter()
+            .position(|o| o == m)
+            .unwrap_or(MODIFIER_ORDER.len())
+    });
+    modifiers.dedup();
+    modifiers
+}
+
+fn modifier_name(mask: &str) -> String {
+    match mask {
+        "Mod4Mask" => "Super".to_string(),
+        "Mod1Mask" => "Alt".to_string(),
+        "ControlMask" => "Ctrl".to_string(),
+        "ShiftMask" => "Shift".to_string(),
+        "LockMask" => "Lock".to_string(),
+        other => other.trim_end_matches("Mask").to_string(),
+    }
+}
+
+fn keysym_name(keysym: &str) -> String {
+    if let Some(xf86) = keysym.strip_prefix("XF86XK_") {
+        format!("XF86{}", xf86)
+    } else {
+        keysym.strip_prefix("XK_").unwrap_or(keysym).to_string()
+    }
+}
+
+fn parse_argument(item: &Item, commands: &HashMap<String, Vec<String>>) -> Argument {
+    let tokens = match item {
+        Item::List(items, ..) => match items.first() {
+            Some(Item::Expr(tokens)) => tokens,
+            _ => return Argument::None,
+        },
+        Item::Expr(tokens) => tokens,
+    };
+
+    // `.field = value`, otherwise positional which initializes `.i`
+    let (field, value) = match &tokens[..] {
+        [dot, Token { tok: Tok::Ident(field), .. }, eq, value @ ..] if dot.is(".") && eq.is("=") => {
+            (field.as_str(), value)
+        }
+        value => ("i", value),
+    };
+
+    match field {
+        "i" => match eval_int(value) {
+            Some(0) if value.len() == 1 => Argument::None,
+            Some(n) => Argument::Int(n as i32),
+            None => symbol(value),
+        },
+        "ui" => match eval_int(value) {
+            Some(n) => Argument::UInt(n as u32),
+            None => symbol(value),
+        },
+        "f" => {
+            let (sign, number) = match value {
+                [sign, number] if sign.is("-") => (-1.0, number),
+                [sign, number] if sign.is("+") => (1.0, number),
+                [number] => (1.0, number),
+                _ => return symbol(value),
+            };
+            match &number.tok {
+                Tok::Number(n) => n
+                    .trim_end_matches(['f', 'F'])
+                    .parse::<f32>()
+                    .map(|f| Argument::Float(sign * f))
+                    .unwrap_or_else(|_| symbol(value)),
+                _ => symbol(value),
+            }
+        }
+        "v" => match value {
+            [amp, Token { tok: Tok::Ident(array), .. }, open, index @ .., close]
+                if amp.is("&") && array == "layouts" && open.is("[") && close.is("]") =>
+            {
+                match eval_int(index) {
+                    Some(n) if n >= 0 => Argument::Layout(n as usize),
+                    _ => symbol(value),
+                }
+            }
+            [Token { tok: Tok::Ident(name), .. }] => match commands.get(name) {
+                Some(argv) => Argument::Command(argv.clone()),
+                None => Argument::Symbol(name.clone()),
+            },
+            _ => {
+                // Compound literal `(const char *[]) { ... }` as produced by SHCMD
+                match value.iter().position(|t| t.is("{")) {
+                    Some(open) => {
+                        let (items, _) = parse_list(value, open + 1);
+                        string_array(&items)
+                            .map(Argument::Command)
+                            .unwrap_or_else(|| symbol(value))
+                    }
+                    None => symbol(value),
+                }
+            }
+        },
+        _ => symbol(value),
+    }
+}
+
+fn symbol(tokens: &[Token]) -> Argument {
+    Argument::Symbol(expr_text(tokens))
+}
+
+/// `tokens` written back out, without the spacing they had.
+fn expr_text(tokens: &[Token]) -> String {
+    let text: Vec<String> = tokens
+        .iter()
+        .map(|t| match &t.tok {
+            Tok::Ident(s) | Tok::Number(s) | Tok::Punct(s) => s.clone(),
+            Tok::Str(s) => format!("{:?}", s),
+        })
+        .collect();
+    text.join("")
+}
+
+/// Evaluates integer constant expressions with `|`, `<<`, unary `-`, `+`, `~` and
+/// parentheses, which covers tag masks like `1 << 8` and `~0`.
+fn eval_int(tokens: &[Token]) -> Option<i64> {
+    let mut pos = 0;
+    let value = eval_or(tokens, &mut pos)?;
+    (pos == tokens.len()).then_some(value)
+}
+
+fn eval_or(tokens: &[Token], pos: &mut usize) -> Option<i64> {
+    let mut value = eval_shift(tokens, pos)?;
+    while tokens.get(*pos).is_some_and(|t| t.is("|")) {
+        *pos += 1;
+        value |= eval_shift(tokens, pos)?;
+    }
+    Some(value)
+}
+
+fn eval_shift(tokens: &[Token], pos: &mut usize) -> Option<i64> {
+    let mut value = eval_unary(tokens, pos)?;
+    while tokens.get(*pos).is_some_and(|t| t.is("<<")) {
+        *pos += 1;
+        value = value.checked_shl(eval_unary(tokens, pos)? as u32)?;
+    }
+    Some(value)
+}
+
+fn eval_unary(tokens: &[Token], pos: &mut usize) -> Option<i64> {
+    let token = tokens.get(*pos)?;
+    *pos += 1;
+    match &token.tok {
+        Tok::Punct(p) if p == "-" => eval_unary(tokens, pos)?.checked_neg(),
+        Tok::Punct(p) if p == "+" => eval_unary(tokens, pos),
+        // dwm's Arg.ui is a 32-bit unsigned int
+        Tok::Punct(p) if p == "~" => Some(!eval_unary(tokens, pos)? & 0xffff_ffff),
+        Tok::Punct(p) if p == "(" => {
+            let value = eval_or(tokens, pos)?;
+            tokens.get(*pos).filter(|t| t.is(")"))?;
+            *pos += 1;
+            Some(value)
+        }
+        Tok::Number(n) => {
+            let n = n.trim_end_matches(['u', 'U', 'l', 'L']);
+            match n.strip_prefix("0x").or_else(|| n.strip_prefix("0X")) {
+                Some(hex) => i64::from_str_radix(hex, 16).ok(),
+                None => n.parse().ok(),
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MACROS: &str = "#define MODKEY Mod4Mask\n\
+        #define TAGKEYS(KEY, TAG) \\\n\
+          {MODKEY, KEY, view, {.ui = 1 << TAG}}, \\\n\
+          {MODKEY | ShiftMask, KEY, tag, {.ui = 1 << TAG}},\n\
+        #define SHCMD(cmd) { .v = (const char *[]) { \"/bin/sh\", \"-c\", cmd, NULL } }\n";
+
+    fn keys(keys: &str) -> Vec<Key> {
+        parse(&format!("{}static const Key keys[] = {{\n{}\n}};\n", MACROS, keys)).keys
+    }
+
+    fn argument(initializer: &str) -> Argument {
+        keys(initializer).remove(0).action.argument
+    }
+
+    #[test]
+    fn modkey_and_masks_become_modifier_names() {
+        let key = keys("{MODKEY | ControlMask | ShiftMask, XK_Return, zoom, {0}},").remove(0);
+        assert_eq!(key.modifiers, ["Super", "Ctrl", "Shift"]);
+        assert_eq!(key.keysym, "Return");
+        assert_eq!(key.action.function, "zoom");
+        assert_eq!(key.action.argument, Argument::None);
+    }
+
+    #[test]
+    fn tagkeys_expands_to_one_key_per_line_of_the_macro() {
+        let keys = keys("TAGKEYS(XK_3, 2)");
+        let parsed: Vec<_> = keys
+            .iter()
+            .map(|key| (key.modifiers.join("+"), key.action.function.as_str(), key.action.argument.clone()))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("Super".to_string(), "view", Argument::UInt(4)),
+                ("Super+Shift".to_string(), "tag", Argument::UInt(4)),
+            ]
+        );
+        assert_eq!(keys[0].keysym, "3");
+        assert_eq!(keys[0].action.source, "TAGKEYS(XK_3, 2)");
+    }
+
+    #[test]
+    fn arguments_are_evaluated() {
+        assert_eq!(argument("{MODKEY, XK_0, view, {.ui = ~0}},"), Argument::UInt(0xffff_ffff));
+        assert_eq!(argument("{MODKEY, XK_h, setmfact, {.f = -0.05}},"), Argument::Float(-0.05));
+        assert_eq!(argument("{MODKEY, XK_j, focusstack, {.i = +1}},"), Argument::Int(1));
+        assert_eq!(argument("{MODKEY, XK_k, focusstack, {.i = -1}},"), Argument::Int(-1));
+        assert_eq!(argument("{MODKEY, XK_9, view, {.ui = 1 << 8}},"), Argument::UInt(256));
+        assert_eq!(argument("{MODKEY, XK_t, setlayout, {.v = &layouts[2]}},"), Argument::Layout(2));
+        assert_eq!(
+            argument("{MODKEY, XK_w, setborderpx, {.i = default_border}},"),
+            Argument::Symbol("default_border".to_string())
+        );
+    }
+
+    #[test]
+    fn shcmd_and_command_arrays_become_argv() {
+        assert_eq!(
+            argument("{MODKEY, XK_p, spawn, SHCMD(\"maim | xclip\")},"),
+            Argument::Command(vec!["/bin/sh".into(), "-c".into(), "maim | xclip".into()])
+        );
+        let config = parse(&format!(
+            "{}static const char *termcmd[] = {{\"st\", \"-e\", \"tmux\", NULL}};\n\
+             static const Key keys[] = {{ {{MODKEY, XK_Return, spawn, {{.v = termcmd}}}}, }};\n",
+            MACROS
+        ));
+        assert_eq!(config.keys[0].action.argument, Argument::Command(vec!["st".into(), "-e".into(), "tmux".into()]));
+    }
+
+    #[test]
+    fn multi_line_initializers_keep_their_line_span() {
+        let keys = keys("{MODKEY, XK_a, togglegaps, {0}},\n{MODKEY | ShiftMask, XK_s, spawn,\n SHCMD(\"maim --select\")},");
+        // MACROS takes 5 lines and the array opens on the 6th
+        assert_eq!((keys[0].action.line, keys[0].action.end_line), (7, 7));
+        assert_eq!((keys[1].action.line, keys[1].action.end_line), (8, 9));
+        assert_eq!(keys[1].action.source, "{MODKEY | ShiftMask, XK_s, spawn,\nSHCMD(\"maim --select\")}");
+    }
+
+    #[test]
+    fn evaluator_rejects_overflow() {
+        // `1 << 63` is i64::MIN, which has no negation
+        assert_eq!(eval_int(&tokenize("-(1 << 63)", 1)), None);
+        assert_eq!(eval_int(&tokenize("-(1 << 62)", 1)), Some(-(1 << 62)));
+        assert_eq!(eval_int(&tokenize("1 << 64", 1)), None);
+    }
+
+    /// `(function, disabled)` of the keys and disabled keys of `keys[]`.
+    fn conditional(keys: &str) -> Vec<(String, bool)> {
+        let config = parse(&format!("{}static const Key keys[] = {{\n{}\n}};\n", MACROS, keys));
+        config
+            .keys
+            .iter()
+            .chain(&config.disabled_keys)
+            .map(|key| (key.action.function.clone(), key.action.disabled))
+            .collect()
+    }
+
+    #[test]
+    fn only_one_branch_of_a_conditional_is_live() {
+        let live = |function: &str| (function.to_string(), false);
+        let dead = |function: &str| (function.to_string(), true);
+        assert_eq!(
+            conditional("#if 1\n{MODKEY, XK_y, togglebar, {0}},\n#else\n{MODKEY, XK_y, zoom, {0}},\n#endif"),
+            [live("togglebar"), dead("zoom")]
+        );
+        assert_eq!(
+            conditional("#if 0\n{MODKEY, XK_y, togglebar, {0}},\n#else\n{MODKEY, XK_y, zoom, {0}},\n#endif"),
+            [live("zoom"), dead("togglebar")]
+        );
+        // PATCH isn't defined in the config, so it may come from a header
+        assert_eq!(
+            conditional("#ifdef PATCH\n{MODKEY, XK_y, togglebar, {0}},\n#else\n{MODKEY, XK_y, zoom, {0}},\n#endif"),
+            [live("togglebar"), dead("zoom")]
+        );
+        assert_eq!(
+            conditional("#ifndef MODKEY\n{MODKEY, XK_y, togglebar, {0}},\n#elif 1\n{MODKEY, XK_y, zoom, {0}},\n#endif"),
+            [live("zoom"), dead("togglebar")]
+        );
+        assert_eq!(
+            conditional("#if 0\n#if 1\n{MODKEY, XK_y, togglebar, {0}},\n#endif\n#endif\n{MODKEY, XK_z, zoom, {0}},"),
+            [live("zoom"), dead("togglebar")]
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use crate::ui::View;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+/// How long the daemon waits for a connection's request line, so a client that
+/// never finishes one can't hold up the ones after it.
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A message from a later invocation to the running instance, sent as one JSON
+/// line per connection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Request {
+    /// Hide the window if it is shown, show it with `View` otherwise.
+    Toggle(View),
+    /// Show and focus the window with `View`.
+    Show(View),
+}
+
+/// A request as sent over the socket, with the options given alongside it that
+/// only take effect when a window starts, e.g. `--overlay`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Message {
+    request: Request,
+    #[serde(default)]
+    startup: Vec<String>,
+}
+
+/// Per-user socket the daemon listens on.
+pub fn socket_path() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join("dwm-cheatsheet.sock"),
+        None => {
+            let user = std::env::var("USER").unwrap_or_default();
+            std::env::temp_dir().join(format!("dwm-cheatsheet-{}.sock", user))
+        }
+    }
+}
+
+/// Hands `request` to a running daemon. Returns false when none is listening,
+/// and the daemon's error when it can't honour one of the `startup` flags.
+pub fn send(request: &Request, startup: &[String]) -> io::Result<bool> {
+    let mut stream = match UnixStream::connect(socket_path()) {
+        Ok(stream) => stream,
+        Err(err)
+            if matches!(
+                err.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused
+            ) =>
+        {
+            return Ok(false)
+        }
+        Err(err) => return Err(err),
+    };
+
+    let message = Message {
+        request: request.clone(),
+        startup: startup.to_vec(),
+    };
+    let mut line = serde_json::to_string(&message)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+
+    // Wait for the daemon to take it, so a quick second toggle can't overtake it
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    match reply.trim_end().strip_prefix("error: ") {
+        Some(err) => Err(io::Error::other(err.to_string())),
+        None => Ok(true),
+    }
+}
+
+/// The bound daemon socket.
+pub struct Listener {
+    listener: UnixListener,
+    /// Startup flags a forwarded request may carry, the ones the daemon itself
+    /// was started with.
+    startup: Vec<String>,
+}
+
+impl Listener {
+    /// Claims the socket. A socket file nobody answers on is left over from a
+    /// crashed daemon and is replaced.
+    pub fn bind(startup: Vec<String>) -> io::Result<Listener> {
+        Listener::bind_to(socket_path(), startup)
+    }
+
+    fn bind_to(path: PathBuf, startup: Vec<String>) -> io::Result<Listener> {
+        if UnixStream::connect(&path).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("a daemon is already listening on {}", path.display()),
+            ));
+        }
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+        Ok(Listener { listener, startup })
+    }
+
+    /// Accepts requests on a background thread and passes each to `on_request`.
+    /// Requests with startup flags the daemon wasn't started with are refused,
+    /// since its window already exists.
+    pub fn serve(self, on_request: impl Fn(Request) + Send + 'static) {
+        thread::spawn(move || {
+            for stream in self.listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+                let mut line = String::new();
+                let mut reader = BufReader::new(&stream);
+                if reader.read_line(&mut line).is_err() {
+                    continue;
+                }
+                match serde_json::from_str::<Message>(&line) {
+                    Ok(message) => match message.startup.iter().find(|flag| !self.startup.contains(flag)) {
+                        Some(flag) => {
+                            let _ = writeln!(
+                                &stream,
+                                "error: the running daemon wasn't started with {}; restart it with that option to use it",
+                                flag
+                            );
+                        }
+                        None => {
+                            on_request(message.request);
+                            let _ = (&stream).write_all(b"ok\n");
+                        }
+                    },
+                    Err(err) => {
+                        let _ = writeln!(&stream, "error: {}", err);
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn request(path: &std::path::Path, line: &str) -> String {
+        let mut stream = UnixStream::connect(path).unwrap();
+        stream.write_all(line.as_bytes()).unwrap();
+        let mut reply = String::new();
+        BufReader::new(stream).read_line(&mut reply).unwrap();
+        reply
+    }
+
+    #[test]
+    fn serve_answers_each_request_despite_a_stalled_client() {
+        let dir = std::env::temp_dir().join(format!("dwm-cheatsheet-serve-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("daemon.sock");
+        let (sender, requests) = mpsc::channel();
+        Listener::bind_to(path.clone(), vec!["--overlay".to_string()])
+            .unwrap()
+            .serve(move |request| sender.send(request).unwrap());
+
+        let _stalled = UnixStream::connect(&path).unwrap();
+        let show = Message {
+            request: Request::Show(View {
+                search: Some("x".to_string()),
+                ..View::default()
+            }),
+            startup: vec!["--overlay".to_string()],
+        };
+        assert_eq!(request(&path, &format!("{}\n", serde_json::to_string(&show).unwrap())), "ok\n");
+        assert_eq!(requests.try_recv().unwrap(), show.request);
+
+        let class = r#"{"request":{"Toggle":{"search":null,"categories":[],"modifier":null}},"startup":["--class x"]}"#;
+        assert!(request(&path, &format!("{}\n", class)).starts_with("error: the running daemon wasn't started with --class x"));
+        assert!(request(&path, "not json\n").starts_with("error: "));
+        assert!(requests.try_recv().is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
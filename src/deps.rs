@@ -0,0 +1,317 @@
+use crate::config::Action;
+use crate::keybindings::Keybinding;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Every program a `spawn` action runs: the argv's own, or each command of an
+/// `SHCMD` line, pipelines, `&&` chains and `$(...)` included.
+pub fn programs(action: &Action) -> Vec<String> {
+    action.command().map_or_else(Vec::new, command_programs)
+}
+
+/// Every program `argv` runs, as [`programs`] finds them.
+pub fn command_programs(argv: &[String]) -> Vec<String> {
+    let found = match argv {
+        [sh, flag, line] if sh.ends_with("sh") && flag == "-c" => shell_programs(line),
+        [program, ..] => vec![program.clone()],
+        [] => Vec::new(),
+    };
+    let mut programs: Vec<String> = Vec::new();
+    for program in found {
+        if !programs.contains(&program) {
+            programs.push(program);
+        }
+    }
+    programs
+}
+
+/// Shell builtins and keywords, which never need installing.
+const BUILTINS: [&str; 22] = [
+    "cd", "echo", "printf", "export", "test", "[", "true", "false", "read", "set", "eval", "source", ".",
+    "if", "then", "else", "fi", "for", "do", "done", "while", "case",
+];
+
+/// Words that run the command after them, e.g. `exec dunst` runs `dunst`.
+const WRAPPERS: [&str; 4] = ["exec", "nohup", "command", "setsid"];
+
+fn shell_programs(line: &str) -> Vec<String> {
+    let mut programs = Vec::new();
+    // With redirections gone, what `&` is left separates commands
+    for segment in strip_redirections(line).split(['|', ';', '&', '\n', '(', ')', '`']) {
+        let words = segment
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c| c == '"' || c == '\'' || c == '$' || c == '{' || c == '}'))
+            // Variable assignments before the command
+            .skip_while(|word| word.contains('=') || WRAPPERS.contains(word));
+        if let Some(program) = words.into_iter().next() {
+            if !program.is_empty() && !BUILTINS.contains(&program) && !program.starts_with('-') {
+                programs.push(program.to_string());
+            }
+        }
+    }
+    programs
+}
+
+/// `line` with its redirections and their targets blanked out: `2>&1`, `>&-`,
+/// `&>/dev/null`, `>> log` and `<file`.
+fn strip_redirections(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::with_capacity(line.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let word_start = i == 0 || chars[i - 1].is_whitespace() || "|;&()`".contains(chars[i - 1]);
+        // The operator's start: `&>`, or `>`/`<` after an optional file descriptor
+        let mut j = i;
+        if chars[i] == '&' && chars.get(i + 1) == Some(&'>') {
+            j += 1;
+        } else if word_start {
+            while j < chars.len() && chars[j].is_ascii_digit() {
+                j += 1;
+            }
+        }
+        if !matches!(chars.get(j), Some('>' | '<')) {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        j += 1;
+        if chars.get(j) == Some(&'>') {
+            j += 1;
+        }
+        if chars.get(j) == Some(&'&') && chars.get(j + 1).is_some_and(|c| c.is_ascii_digit() || *c == '-') {
+            // Duplicating a descriptor, e.g. `2>&1`
+            j += 1;
+            while j < chars.len() && (chars[j].is_ascii_digit() || chars[j] == '-') {
+                j += 1;
+            }
+        } else {
+            if chars.get(j) == Some(&'&') {
+                j += 1;
+            }
+            while j < chars.len() && chars[j].is_whitespace() {
+                j += 1;
+            }
+            while j < chars.len() && !chars[j].is_whitespace() && !"|;&()`".contains(chars[j]) {
+                j += 1;
+            }
+        }
+        out.push(' ');
+        i = j;
+    }
+    out
+}
+
+/// Where `program` is found: the path itself when it has a slash (with `~`
+/// expanded), otherwise the first executable of that name on `$PATH`.
+pub fn resolve(program: &str) -> Option<PathBuf> {
+    if program.contains('/') {
+        let path = match program.strip_prefix("~/") {
+            Some(rest) => PathBuf::from(env::var_os("HOME")?).join(rest),
+            None => PathBuf::from(program),
+        };
+        return is_executable(&path).then_some(path);
+    }
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|path| is_executable(path))
+}
+
+fn is_executable(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+/// Programs the bindings run that aren't installed, each with the combos that
+/// need it, sorted by name.
+pub fn missing(keybindings: &[Keybinding]) -> BTreeMap<String, Vec<String>> {
+    let mut missing: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut found: BTreeMap<String, bool> = BTreeMap::new();
+    for keybinding in keybindings {
+        let Some(action) = keybinding.action.as_ref().filter(|action| !action.disabled) else {
+            continue;
+        };
+        for program in programs(action) {
+            let installed = *found.entry(program.clone()).or_insert_with(|| resolve(&program).is_some());
+            if !installed {
+                missing.entry(program).or_default().push(keybinding.combo());
+            }
+        }
+    }
+    missing
+}
+
+/// Package providing `program` on Arch, Debian/Ubuntu and Fedora, for the
+/// tools chadwm configs commonly call. `None` where it isn't packaged there,
+/// and `aur:` for packages in the AUR rather than Arch's repositories.
+const PACKAGES: &[(&str, [Option<&str>; 3])] = &[
+    ("maim", [Some("maim"), Some("maim"), Some("maim")]),
+    ("xclip", [Some("xclip"), Some("xclip"), Some("xclip")]),
+    ("xdotool", [Some("xdotool"), Some("xdotool"), Some("xdotool")]),
+    ("playerctl", [Some("playerctl"), Some("playerctl"), Some("playerctl")]),
+    ("pactl", [Some("libpulse"), Some("pulseaudio-utils"), Some("pulseaudio-utils")]),
+    ("pamixer", [Some("pamixer"), Some("pamixer"), Some("pamixer")]),
+    ("amixer", [Some("alsa-utils"), Some("alsa-utils"), Some("alsa-utils")]),
+    ("wpctl", [Some("wireplumber"), Some("wireplumber"), Some("wireplumber")]),
+    ("light", [Some("light"), Some("light"), Some("light")]),
+    ("brightnessctl", [Some("brightnessctl"), Some("brightnessctl"), Some("brightnessctl")]),
+    ("xbacklight", [Some("xorg-xbacklight"), Some("xbacklight"), Some("xbacklight")]),
+    ("betterlockscreen", [Some("aur:betterlockscreen"), None, None]),
+    ("slock", [Some("slock"), Some("suckless-tools"), Some("slock")]),
+    ("i3lock", [Some("i3lock"), Some("i3lock"), Some("i3lock")]),
+    ("dm-maim", [Some("aur:dmscripts"), None, None]),
+    ("dm-note", [Some("aur:dmscripts"), None, None]),
+    ("rofi", [Some("rofi"), Some("rofi"), Some("rofi")]),
+    ("dmenu", [Some("dmenu"), Some("suckless-tools"), Some("dmenu")]),
+    ("st", [Some("st"), Some("stterm"), Some("st")]),
+    ("alacritty", [Some("alacritty"), Some("alacritty"), Some("alacritty")]),
+    ("kitty", [Some("kitty"), Some("kitty"), Some("kitty")]),
+    ("firefox", [Some("firefox"), Some("firefox-esr"), Some("firefox")]),
+    ("thunar", [Some("thunar"), Some("thunar"), Some("thunar")]),
+    ("code", [Some("code"), None, None]),
+    ("eww", [Some("aur:eww"), None, None]),
+    ("flameshot", [Some("flameshot"), Some("flameshot"), Some("flameshot")]),
+    ("scrot", [Some("scrot"), Some("scrot"), Some("scrot")]),
+    ("notify-send", [Some("libnotify"), Some("libnotify-bin"), Some("libnotify")]),
+    ("dunstify", [Some("dunst"), Some("dunst"), Some("dunst")]),
+    ("feh", [Some("feh"), Some("feh"), Some("feh")]),
+    ("nvim", [Some("neovim"), Some("neovim"), Some("neovim")]),
+    ("pcmanfm", [Some("pcmanfm"), Some("pcmanfm"), Some("pcmanfm")]),
+];
+
+/// The distributions [`PACKAGES`] has columns for, with their install command.
+const DISTROS: [(&str, &str); 3] = [("arch", "pacman -S"), ("debian", "apt install"), ("fedora", "dnf install")];
+
+/// How to install `program`, looked up by its file name: the package for this
+/// system's distribution when known, otherwise for each distribution that has one.
+pub fn install_hint(program: &str) -> Option<String> {
+    let name = program.rsplit('/').next().unwrap_or(program);
+    let (_, packages) = PACKAGES.iter().find(|(known, _)| *known == name)?;
+    let hints: Vec<(usize, String)> = DISTROS
+        .iter()
+        .zip(packages)
+        .enumerate()
+        .filter_map(|(i, ((_, install), package))| {
+            let package = package.as_ref()?;
+            let hint = match package.strip_prefix("aur:") {
+                Some(package) => format!("{} from the AUR", package),
+                None => format!("{} {}", install, package),
+            };
+            Some((i, hint))
+        })
+        .collect();
+    let distro = distro();
+    match hints.iter().find(|(i, _)| Some(*i) == distro) {
+        Some((_, hint)) => Some(hint.clone()),
+        None if hints.is_empty() => None,
+        None => Some(
+            hints
+                .iter()
+                .map(|(i, hint)| format!("{}: {}", DISTROS[*i].0, hint))
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+    }
+}
+
+/// This system's column in [`PACKAGES`], from `ID` and `ID_LIKE` in
+/// /etc/os-release, so Manjaro counts as Arch and Ubuntu as Debian.
+fn distro() -> Option<usize> {
+    let release = fs::read_to_string("/etc/os-release").ok()?;
+    release
+        .lines()
+        .filter_map(|line| line.strip_prefix("ID=").or_else(|| line.strip_prefix("ID_LIKE=")))
+        .flat_map(|ids| ids.trim_matches('"').split_whitespace())
+        .find_map(|id| DISTROS.iter().position(|(distro, _)| *distro == id))
+}
+
+/// Prints each missing program with the combos needing it and an install hint.
+pub fn print_missing(keybindings: &[Keybinding]) -> io::Result<()> {
+    let missing = missing(keybindings);
+    let mut stdout = io::stdout().lock();
+    if missing.is_empty() {
+        return writeln!(stdout, "All programs the bindings run are installed");
+    }
+    for (program, combos) in &missing {
+        writeln!(stdout, "{} (used by {})", program, combos.join(", "))?;
+        if let Some(hint) = install_hint(program) {
+            writeln!(stdout, "  {}", hint)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Argument;
+
+    fn spawn(argv: &[&str]) -> Vec<String> {
+        let action = Action {
+            function: "spawn".to_string(),
+            argument: Argument::Command(argv.iter().map(|arg| arg.to_string()).collect()),
+            line: 1,
+            end_line: 1,
+            source: String::new(),
+            file: None,
+            disabled: false,
+        };
+        programs(&action)
+    }
+
+    fn programs_of(line: &str) -> Vec<String> {
+        spawn(&["/bin/sh", "-c", line])
+    }
+
+    #[test]
+    fn shell_lines_yield_every_command() {
+        assert_eq!(programs_of("maim --select | xclip -selection clipboard"), ["maim", "xclip"]);
+        assert_eq!(programs_of("pamixer -i 5 && notify-send Volume"), ["pamixer", "notify-send"]);
+        assert_eq!(programs_of("notify-send \"$(date +%H:%M)\""), ["notify-send", "date"]);
+        assert_eq!(programs_of("LANG=C GTK_THEME=x thunar"), ["thunar"]);
+        assert_eq!(programs_of("exec setsid nohup dunst"), ["dunst"]);
+        assert_eq!(programs_of("cd ~ && feh x.png; xclip & feh y.png"), ["feh", "xclip"]);
+    }
+
+    #[test]
+    fn redirections_are_not_programs() {
+        assert_eq!(programs_of("maim 2>&1 | xclip"), ["maim", "xclip"]);
+        assert_eq!(programs_of("dunst &>/dev/null"), ["dunst"]);
+        assert_eq!(programs_of("dunst &> /dev/null &"), ["dunst"]);
+        assert_eq!(programs_of("scrot >> ~/shots.log 2> /dev/null"), ["scrot"]);
+        assert_eq!(programs_of("xclip -i </tmp/text >&- && rofi"), ["xclip", "rofi"]);
+        assert_eq!(programs_of("slock >/dev/null 2>&1"), ["slock"]);
+    }
+
+    #[test]
+    fn argv_commands_yield_their_program() {
+        assert_eq!(spawn(&["st", "-e", "tmux"]), ["st"]);
+    }
+
+    #[test]
+    fn resolve_finds_executable_paths() {
+        let dir = env::temp_dir().join(format!("dwm-cheatsheet-deps-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("script");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        let path = script.to_str().unwrap();
+
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(resolve(path), None);
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(resolve(path), Some(script.clone()));
+        assert_eq!(resolve(dir.to_str().unwrap()), None);
+        assert_eq!(resolve("/nonexistent/program"), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn install_hint_names_the_package() {
+        assert!(install_hint("/usr/bin/maim").is_some_and(|hint| hint.ends_with("maim")));
+        assert!(install_hint("code").is_some_and(|hint| hint.contains("pacman -S code")));
+        assert!(install_hint("betterlockscreen").is_some_and(|hint| hint.contains("betterlockscreen from the AUR")));
+        assert_eq!(install_hint("no-such-program"), None);
+    }
+}
@@ -0,0 +1,263 @@
+use crate::config::{Action, Argument, Layout};
+use crate::keybindings::Category;
+
+/// A name and a sentence for a binding found only in the config, from what
+/// stock dwm and common patches do with the function and argument, e.g.
+/// `setmfact {.f = -0.05}` is "Shrink Master", "Shrink master area by 5%".
+///
+/// Functions it doesn't know are named after themselves.
+pub fn describe(action: &Action, layouts: &[Layout]) -> (String, String) {
+    let argument = &action.argument;
+    let int = match argument {
+        Argument::Int(n) => *n,
+        _ => 0,
+    };
+    let float = match argument {
+        Argument::Float(f) => *f,
+        _ => 0.0,
+    };
+    let (name, description) = match action.function.as_str() {
+        "view" => match tags(argument) {
+            Tags::None => ("Last Tag".into(), "Switch back to the previously viewed tags".into()),
+            Tags::All => ("View All Tags".into(), "View all tags".into()),
+            Tags::Some(tags) | Tags::Other(tags) => (format!("View Tag {}", tags), format!("View tag {}", tags)),
+        },
+        "toggleview" => match tags(argument) {
+            Tags::All => ("Toggle All Tags".into(), "Show or hide the windows of every tag".into()),
+            Tags::Some(tags) | Tags::Other(tags) => (
+                format!("Toggle Tag {} View", tags),
+                format!("Show or hide tag {}'s windows alongside the current tags", tags),
+            ),
+            Tags::None => ("Toggle View".into(), "Toggle no tags".into()),
+        },
+        "tag" => match tags(argument) {
+            Tags::All => ("Tag All".into(), "Show the focused window on all tags".into()),
+            Tags::Some(tags) | Tags::Other(tags) => {
+                (format!("Move to Tag {}", tags), format!("Move the focused window to tag {}", tags))
+            }
+            Tags::None => ("Tag".into(), "Tag the focused window with nothing".into()),
+        },
+        "toggletag" => match tags(argument) {
+            Tags::All => ("Toggle All Tags".into(), "Add or remove every tag on the focused window".into()),
+            Tags::Some(tags) | Tags::Other(tags) => (
+                format!("Toggle Tag {}", tags),
+                format!("Add or remove tag {} on the focused window", tags),
+            ),
+            Tags::None => ("Toggle Tag".into(), "Toggle no tags on the focused window".into()),
+        },
+        "setmfact" if float > 1.0 => (
+            "Set Master".into(),
+            format!("Set master area to {}% of the screen", percent(float - 1.0)),
+        ),
+        "setmfact" if float < 0.0 => ("Shrink Master".into(), format!("Shrink master area by {}%", percent(-float))),
+        "setmfact" => ("Expand Master".into(), format!("Grow master area by {}%", percent(float))),
+        "setcfact" if float == 0.0 => ("Reset Client Size".into(), "Reset the focused window's size weight".into()),
+        "setcfact" if float < 0.0 => (
+            "Shrink Client".into(),
+            format!("Decrease the focused window's size weight by {}", -float),
+        ),
+        "setcfact" => ("Grow Client".into(), format!("Increase the focused window's size weight by {}", float)),
+        "incnmaster" if int < 0 => ("Dec Master".into(), format!("Remove {} from the master area", windows(-int))),
+        "incnmaster" => ("Inc Master".into(), format!("Add {} to the master area", windows(int))),
+        "focusstack" if int < 0 => ("Focus Previous".into(), "Focus the previous window in the stack".into()),
+        "focusstack" => ("Focus Next".into(), "Focus the next window in the stack".into()),
+        "movestack" if int < 0 => ("Move Up".into(), "Move the focused window up the stack".into()),
+        "movestack" => ("Move Down".into(), "Move the focused window down the stack".into()),
+        "shiftview" if int < 0 => ("Previous Tag".into(), "View the previous tag".into()),
+        "shiftview" => ("Next Tag".into(), "View the next tag".into()),
+        "cyclelayout" if int < 0 => ("Previous Layout".into(), "Switch to the previous layout".into()),
+        "cyclelayout" => ("Next Layout".into(), "Switch to the next layout".into()),
+        "setlayout" => match argument {
+            Argument::Layout(index) => match layouts.get(*index) {
+                Some(layout) => (
+                    format!("{} Layout", layout.symbol),
+                    match &layout.arrange {
+                        Some(arrange) => format!("Switch to the {} layout ({})", layout.symbol, arrange),
+                        None => format!("Switch to the {} floating layout", layout.symbol),
+                    },
+                ),
+                None => (format!("Layout {}", index), format!("Switch to layout {}", index)),
+            },
+            _ => ("Toggle Layout".into(), "Switch back to the previous layout".into()),
+        },
+        "setborderpx" => match argument {
+            Argument::Int(n) if *n < 0 => ("Decrease Border".into(), format!("Thin window borders by {}px", -n)),
+            Argument::Int(n) => ("Increase Border".into(), format!("Thicken window borders by {}px", n)),
+            _ => ("Default Border".into(), "Reset window borders to their default width".into()),
+        },
+        "hidewin" => ("Hide Window".into(), "Hide the focused window".into()),
+        "restorewin" => ("Restore Window".into(), "Restore the last hidden window".into()),
+        "togglegaps" => ("Toggle Gaps".into(), "Turn gaps on or off".into()),
+        "defaultgaps" => ("Default Gaps".into(), "Reset gaps to their default size".into()),
+        function if function.starts_with("incr") && function.ends_with("gaps") => {
+            let which = match function {
+                "incrgaps" => "all",
+                "incrigaps" => "inner",
+                "incrogaps" => "outer",
+                "incrihgaps" => "inner horizontal",
+                "incrivgaps" => "inner vertical",
+                "incrohgaps" => "outer horizontal",
+                "incrovgaps" => "outer vertical",
+                _ => "the",
+            };
+            let (verb, change) = if int < 0 { ("Decrease", "Shrink") } else { ("Increase", "Widen") };
+            (
+                format!("{} {} Gaps", verb, title_case(which)),
+                format!("{} {} gaps by {}px", change, which, int.abs()),
+            )
+        }
+        "tabmode" => ("Tab Mode".into(), "Cycle the tab bar between shown, hidden and automatic".into()),
+        "focusmon" if int < 0 => ("Previous Monitor".into(), "Focus the previous monitor".into()),
+        "focusmon" => ("Next Monitor".into(), "Focus the next monitor".into()),
+        "tagmon" if int < 0 => ("Send to Previous Monitor".into(), "Move the focused window to the previous monitor".into()),
+        "tagmon" => ("Send to Next Monitor".into(), "Move the focused window to the next monitor".into()),
+        "zoom" => ("Zoom Master".into(), "Swap the focused window with the master".into()),
+        "killclient" => ("Kill Window".into(), "Close the focused window".into()),
+        "togglebar" => ("Toggle Bar".into(), "Show or hide the bar".into()),
+        "togglefloating" => ("Toggle Floating".into(), "Float or tile the focused window".into()),
+        "togglefullscr" | "togglefullscreen" => ("Toggle Fullscreen".into(), "Toggle fullscreen for the focused window".into()),
+        "quit" => ("Quit DWM".into(), "Exit dwm".into()),
+        "spawn" => match action.shell_command() {
+            Some(command) => {
+                let program = action.command().and_then(|argv| argv.first()).map_or("", String::as_str);
+                let program = match program {
+                    "/bin/sh" | "sh" => command.split_whitespace().next().unwrap_or(""),
+                    _ => program,
+                };
+                let program = program.rsplit('/').next().unwrap_or(program);
+                (format!("Run {}", program), format!("Run `{}`", command))
+            }
+            None => ("Run Command".into(), format!("Run {}", argument)),
+        },
+        function => {
+            let argument = argument.to_string();
+            let description = if argument.is_empty() {
+                format!("Call {}", function)
+            } else {
+                format!("Call {} with {}", function, argument)
+            };
+            (function.to_string(), description)
+        }
+    };
+    (name, description)
+}
+
+/// Which category a config-only binding belongs in.
+pub fn category(action: &Action, keysym: &str) -> Category {
+    match action.function.as_str() {
+        "spawn" if keysym.starts_with("XF86Audio") || keysym.starts_with("XF86MonBrightness") => Category::Media,
+        "spawn" if keysym == "Print" => Category::Screenshot,
+        "spawn" => Category::Applications,
+        "view" | "toggleview" | "tag" | "toggletag" | "shiftview" => Category::Tags,
+        "setmfact" | "setcfact" | "incnmaster" | "setlayout" | "cyclelayout" | "tabmode" => Category::Layout,
+        "focusstack" | "focusmon" | "tagmon" => Category::Navigation,
+        "setborderpx" => Category::Borders,
+        "quit" | "togglebar" => Category::System,
+        function if function.contains("gaps") => Category::Gaps,
+        _ => Category::WindowManagement,
+    }
+}
+
+enum Tags {
+    /// `{0}`, which `view` takes as the previous tagset.
+    None,
+    /// `~0`
+    All,
+    /// Tag numbers, e.g. `3` or `1, 2`.
+    Some(String),
+    /// An argument that isn't a tag mask, shown as written.
+    Other(String),
+}
+
+fn tags(argument: &Argument) -> Tags {
+    let mask = match argument {
+        Argument::None => return Tags::None,
+        Argument::UInt(mask) => *mask,
+        Argument::Int(mask) => *mask as u32,
+        other => return Tags::Other(other.to_string()),
+    };
+    match mask {
+        0 => Tags::None,
+        u32::MAX => Tags::All,
+        mask => Tags::Some(
+            (0..32)
+                .filter(|bit| mask & (1 << bit) != 0)
+                .map(|bit| (bit + 1).to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+    }
+}
+
+fn percent(fraction: f32) -> String {
+    let percent = (fraction * 1000.0).round() / 10.0;
+    percent.to_string()
+}
+
+fn windows(n: i32) -> String {
+    if n == 1 {
+        "a window".to_string()
+    } else {
+        format!("{} windows", n)
+    }
+}
+
+fn title_case(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(function: &str, argument: Argument) -> Action {
+        Action {
+            function: function.to_string(),
+            argument,
+            line: 1,
+            end_line: 1,
+            source: String::new(),
+            file: None,
+            disabled: false,
+        }
+    }
+
+    #[test]
+    fn setmfact_by_a_negative_fraction_shrinks_the_master_area() {
+        let (name, description) = describe(&action("setmfact", Argument::Float(-0.05)), &[]);
+        assert_eq!(name, "Shrink Master");
+        assert_eq!(description, "Shrink master area by 5%");
+    }
+
+    #[test]
+    fn view_of_every_tag_bit_views_all_tags() {
+        let (name, description) = describe(&action("view", Argument::UInt(!0)), &[]);
+        assert_eq!(name, "View All Tags");
+        assert_eq!(description, "View all tags");
+    }
+
+    #[test]
+    fn percent_rounds_to_a_tenth() {
+        assert_eq!(percent(0.05), "5");
+        assert_eq!(percent(0.025), "2.5");
+        assert_eq!(percent(0.3333), "33.3");
+    }
+
+    #[test]
+    fn tags_lists_the_bits_of_a_mask() {
+        assert!(matches!(tags(&Argument::None), Tags::None));
+        assert!(matches!(tags(&Argument::UInt(0)), Tags::None));
+        assert!(matches!(tags(&Argument::Int(-1)), Tags::All));
+        assert!(matches!(tags(&Argument::UInt(1 << 2)), Tags::Some(tags) if tags == "3"));
+        assert!(matches!(tags(&Argument::UInt(0b11)), Tags::Some(tags) if tags == "1, 2"));
+        assert!(matches!(tags(&Argument::Float(0.5)), Tags::Other(tags) if tags == ".f = 0.5"));
+    }
+}
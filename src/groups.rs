@@ -0,0 +1,117 @@
+use crate::keybindings::{Category, Keybinding};
+use serde::{Deserialize, Serialize};
+
+/// What the cheatsheet groups bindings into cards by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GroupBy {
+    Category,
+    /// Modifier layer, e.g. everything on Super+Shift.
+    Modifiers,
+    /// Everything bound to the same key.
+    Key,
+    /// The dwm function called, e.g. every `incrgaps` binding.
+    Function,
+}
+
+impl GroupBy {
+    pub const ALL: [GroupBy; 4] = [GroupBy::Category, GroupBy::Modifiers, GroupBy::Key, GroupBy::Function];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GroupBy::Category => "Category",
+            GroupBy::Modifiers => "Modifiers",
+            GroupBy::Key => "Key",
+            GroupBy::Function => "dwm function",
+        }
+    }
+
+    fn group_of(self, keybinding: &Keybinding) -> Group {
+        match self {
+            GroupBy::Category => Group::Category(keybinding.category.clone()),
+            GroupBy::Modifiers => Group::Modifiers(keybinding.modifiers.clone()),
+            // XK_i and XK_I are the same key
+            GroupBy::Key if keybinding.key.chars().count() == 1 => Group::Key(keybinding.key.to_lowercase()),
+            GroupBy::Key => Group::Key(keybinding.key.clone()),
+            GroupBy::Function => Group::Function(keybinding.action.as_ref().map(|a| a.function.clone())),
+        }
+    }
+}
+
+/// One card's worth of bindings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Group {
+    Category(Category),
+    /// Modifier names in display order, empty for bindings without any.
+    Modifiers(Vec<String>),
+    /// Key name, single letters lowercased.
+    Key(String),
+    /// dwm function, `None` for bindings missing from the config.
+    Function(Option<String>),
+}
+
+impl Group {
+    pub fn title(&self) -> String {
+        match self {
+            Group::Category(category) => category.as_str().to_string(),
+            Group::Modifiers(modifiers) if modifiers.is_empty() => "No modifier".to_string(),
+            Group::Modifiers(modifiers) => modifiers.join("+"),
+            Group::Key(key) if key.is_empty() => "No key".to_string(),
+            Group::Key(key) => key.clone(),
+            Group::Function(Some(function)) => function.clone(),
+            Group::Function(None) => "Not in config".to_string(),
+        }
+    }
+
+    /// The category's accent colour; generated groups take one from the same
+    /// palette, picked by their title so a group keeps its colour.
+    pub fn rgb(&self) -> [u8; 3] {
+        match self {
+            Group::Category(category) => category.rgb(),
+            _ => {
+                let hash = self
+                    .title()
+                    .bytes()
+                    .fold(0usize, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte as usize));
+                Category::ALL[hash % Category::ALL.len()].rgb()
+            }
+        }
+    }
+
+    /// Where the group sorts among the others of its kind: Super layers before
+    /// other modifiers and no modifier last, keys and functions alphabetically.
+    fn sort_key(&self, order: &[Category]) -> (bool, bool, usize, String) {
+        match self {
+            Group::Category(category) => {
+                let position = order.iter().position(|c| c == category).unwrap_or(order.len());
+                (false, false, position, String::new())
+            }
+            Group::Modifiers(modifiers) => (
+                modifiers.is_empty(),
+                !modifiers.iter().any(|m| m == "Super"),
+                modifiers.len(),
+                modifiers.join("+"),
+            ),
+            Group::Key(key) => (key.is_empty(), false, 0, key.to_lowercase()),
+            Group::Function(function) => (function.is_none(), false, 0, function.clone().unwrap_or_default()),
+        }
+    }
+}
+
+/// Groups `keybindings` into cards, in the order the cards are shown. Bindings
+/// keep their order within a card, and categories follow `order`.
+pub fn group<'a>(
+    keybindings: impl IntoIterator<Item = &'a Keybinding>,
+    by: GroupBy,
+    order: &[Category],
+) -> Vec<(Group, Vec<Keybinding>)> {
+    let mut groups: Vec<(Group, Vec<Keybinding>)> = Vec::new();
+    for keybinding in keybindings {
+        let group = by.group_of(keybinding);
+        match groups.iter_mut().find(|(g, _)| *g == group) {
+            Some((_, members)) => members.push(keybinding.clone()),
+            None => groups.push((group, vec![keybinding.clone()])),
+        }
+    }
+    groups.sort_by_cached_key(|(group, _)| group.sort_key(order));
+    groups
+}
@@ -0,0 +1,392 @@
+use crate::config::{Action, Argument, DwmConfig, Layout};
+use crate::keybindings::Keybinding;
+use crate::monitor::Geometry;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// Where the dwm-ipc patch listens unless `ipcsockpath` says otherwise.
+pub const DEFAULT_SOCKET: &str = "/tmp/dwm.sock";
+
+/// Every message starts with this, followed by the payload size and type.
+const MAGIC: &[u8; 7] = b"DWM-IPC";
+
+/// How long a request may wait for dwm before the connection is given up.
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Largest reply accepted; dwm's are a few KB even with many clients.
+const MAX_REPLY: u32 = 1 << 20;
+
+/// How often the window refreshes the live state.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Message types, dwm-ipc's `IPC_TYPE_*`
+const RUN_COMMAND: u8 = 0;
+const GET_MONITORS: u8 = 1;
+const GET_TAGS: u8 = 2;
+const GET_LAYOUTS: u8 = 3;
+const GET_DWM_CLIENT: u8 = 4;
+
+/// Functions that act on the selected monitor rather than the focused client.
+/// While the cheatsheet is open it is the focused client, so `killclient`,
+/// `tag`, `zoom` and the like would act on it instead; those are replayed as
+/// key presses after it closes.
+const MONITOR_FUNCTIONS: [&str; 17] = [
+    "setlayout", "cyclelayout", "togglegaps", "defaultgaps", "incrgaps", "incrigaps", "incrogaps", "incrihgaps",
+    "incrivgaps", "incrohgaps", "incrovgaps", "view", "toggleview", "shiftview", "setmfact", "incnmaster",
+    "togglebar",
+];
+
+/// Functions in [`MONITOR_FUNCTIONS`] dwm-ipc registers without an argument. The
+/// others take a number, so their `{0}` is sent as `0` rather than no argument.
+const NO_ARGUMENT_FUNCTIONS: [&str; 4] = ["setlayout", "togglegaps", "defaultgaps", "togglebar"];
+
+/// Whether `action` can be sent as a `run_command`: a monitor-level function
+/// whose argument is a number or a layout. `spawn` and unresolved pointers can't
+/// be expressed in the protocol.
+pub fn can_run(action: &Action) -> bool {
+    MONITOR_FUNCTIONS.contains(&action.function.as_str())
+        && matches!(
+            action.argument,
+            Argument::None
+                | Argument::Int(_)
+                | Argument::UInt(_)
+                | Argument::Float(_)
+                | Argument::Layout(_)
+        )
+}
+
+/// The socket dwm listens on according to `config`.
+pub fn socket_path(config: &DwmConfig) -> PathBuf {
+    PathBuf::from(config.ipc_socket.as_deref().unwrap_or(DEFAULT_SOCKET))
+}
+
+/// What dwm currently shows on the selected monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DwmState {
+    pub monitor: i32,
+    /// Names of the selected tags.
+    pub tags: Vec<String>,
+    /// Symbol of the current layout, e.g. `[]=`.
+    pub layout: String,
+    pub focused: Option<Client>,
+}
+
+/// The focused client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Client {
+    pub name: String,
+    pub floating: bool,
+    pub fullscreen: bool,
+}
+
+impl DwmState {
+    /// Whether the current layout is one without an arrange function. Without a
+    /// matching `layouts[]` entry dwm's default floating symbol is assumed.
+    pub fn is_floating_layout(&self, layouts: &[Layout]) -> bool {
+        match layouts.iter().find(|layout| layout.symbol == self.layout) {
+            Some(layout) => layout.arrange.is_none(),
+            None => self.layout == "><>",
+        }
+    }
+
+    /// Whether `keybinding` is one the user is likely to want right now: the
+    /// layout switches while floating, and the toggles that undo the focused
+    /// client's floating or fullscreen state.
+    pub fn is_relevant(&self, keybinding: &Keybinding, layouts: &[Layout]) -> bool {
+        let Some(action) = &keybinding.action else {
+            return false;
+        };
+        let focused = self.focused.as_ref();
+        match action.function.as_str() {
+            "setlayout" => self.is_floating_layout(layouts),
+            "togglefloating" => focused.is_some_and(|client| client.floating),
+            "togglefullscr" | "togglefakefullscreen" => {
+                focused.is_some_and(|client| client.fullscreen)
+            }
+            _ => false,
+        }
+    }
+
+    /// One line for the header, e.g. `Tags 1 3 · []= · Firefox (floating)`.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            format!("Tags {}", self.tags.join(" ")),
+            self.layout.clone(),
+        ];
+        if let Some(client) = &self.focused {
+            let mut states = Vec::new();
+            if client.floating {
+                states.push("floating");
+            }
+            if client.fullscreen {
+                states.push("fullscreen");
+            }
+            if states.is_empty() {
+                parts.push(client.name.clone());
+            } else {
+                parts.push(format!("{} ({})", client.name, states.join(", ")));
+            }
+        }
+        parts.join(" · ")
+    }
+}
+
+/// A connection to dwm's IPC socket.
+pub struct IpcClient {
+    stream: UnixStream,
+}
+
+impl IpcClient {
+    pub fn connect(path: &Path) -> io::Result<IpcClient> {
+        let stream = UnixStream::connect(path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        Ok(IpcClient { stream })
+    }
+
+    /// Reads the selected monitor's tags, layout and focused client.
+    pub fn state(&mut self) -> io::Result<DwmState> {
+        let monitors: Vec<Monitor> = self.request(GET_MONITORS, "")?;
+        let monitor = monitors
+            .into_iter()
+            .find(|monitor| monitor.is_selected)
+            .ok_or_else(|| invalid_data("dwm reported no selected monitor"))?;
+
+        let tags: Vec<Tag> = self.request(GET_TAGS, "")?;
+        let tags = tags
+            .into_iter()
+            .filter(|tag| monitor.tagset.current & tag.bit_mask != 0)
+            .map(|tag| tag.name)
+            .collect();
+
+        let focused = match monitor.clients.selected {
+            0 => None,
+            window => {
+                let payload = format!("{{\"client_window_id\":{}}}", window);
+                let client: DwmClient = self.request(GET_DWM_CLIENT, &payload)?;
+                Some(Client {
+                    name: client.name,
+                    floating: client.states.is_floating,
+                    fullscreen: client.states.is_fullscreen,
+                })
+            }
+        };
+
+        Ok(DwmState {
+            monitor: monitor.num,
+            tags,
+            layout: monitor.layout.symbol.current,
+            focused,
+        })
+    }
+
+    /// The area of dwm's selected monitor.
+    pub fn selected_monitor(&mut self) -> io::Result<Geometry> {
+        let monitors: Vec<Monitor> = self.request(GET_MONITORS, "")?;
+        monitors
+            .into_iter()
+            .find(|monitor| monitor.is_selected)
+            .map(|monitor| monitor.monitor_geometry)
+            .ok_or_else(|| invalid_data("dwm reported no selected monitor"))
+    }
+
+    /// The area of every monitor, in dwm's numbering.
+    pub fn monitors(&mut self) -> io::Result<Vec<Geometry>> {
+        let mut monitors: Vec<Monitor> = self.request(GET_MONITORS, "")?;
+        monitors.sort_by_key(|monitor| monitor.num);
+        Ok(monitors.into_iter().map(|monitor| monitor.monitor_geometry).collect())
+    }
+
+    /// Calls the binding's function in dwm, e.g. `view` with its tag mask. Layouts
+    /// are passed by address through the patch's `setlayoutsafe`, which checks it
+    /// against `layouts[]`. Returns the command that was sent.
+    pub fn run_command(&mut self, action: &Action) -> io::Result<String> {
+        let (command, args) = match action.argument {
+            _ if !can_run(action) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("{} can't be run over IPC", action.function),
+                ))
+            }
+            Argument::Layout(index) => {
+                let layouts: Vec<IpcLayout> = self.request(GET_LAYOUTS, "")?;
+                let layout = layouts.get(index).ok_or_else(|| {
+                    invalid_data(&format!("dwm has no layout {}", index))
+                })?;
+                ("setlayoutsafe", json!([layout.address]))
+            }
+            Argument::Int(value) => (action.function.as_str(), json!([value])),
+            Argument::UInt(value) => (action.function.as_str(), json!([value])),
+            Argument::Float(value) => (action.function.as_str(), json!([value])),
+            _ if NO_ARGUMENT_FUNCTIONS.contains(&action.function.as_str()) => (action.function.as_str(), json!([])),
+            _ if action.function == "setmfact" => (action.function.as_str(), json!([0.0])),
+            _ => (action.function.as_str(), json!([0])),
+        };
+
+        let payload = json!({ "command": command, "args": args }).to_string();
+        let reply: CommandResult = self.request(RUN_COMMAND, &payload)?;
+        match reply.result.as_str() {
+            "success" => Ok(format!("{} {}", command, args)),
+            _ => Err(io::Error::other(format!(
+                "dwm refused {}: {}",
+                command,
+                reply.reason.as_deref().unwrap_or(&reply.result)
+            ))),
+        }
+    }
+
+    /// Sends one message and decodes dwm's JSON reply.
+    fn request<T: DeserializeOwned>(&mut self, kind: u8, payload: &str) -> io::Result<T> {
+        // dwm-ipc's header is packed with the size in native byte order
+        let mut message = Vec::with_capacity(MAGIC.len() + 5 + payload.len());
+        message.extend_from_slice(MAGIC);
+        message.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+        message.push(kind);
+        message.extend_from_slice(payload.as_bytes());
+        self.stream.write_all(&message)?;
+
+        let mut header = [0; MAGIC.len() + 5];
+        self.stream.read_exact(&mut header)?;
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err(invalid_data("reply does not start with DWM-IPC"));
+        }
+        let size = u32::from_ne_bytes(header[MAGIC.len()..MAGIC.len() + 4].try_into().unwrap());
+        if header[MAGIC.len() + 4] != kind {
+            return Err(invalid_data("reply has an unexpected message type"));
+        }
+        // A corrupt header mustn't make us allocate gigabytes
+        if size > MAX_REPLY {
+            return Err(invalid_data(&format!("reply of {} bytes is implausibly large", size)));
+        }
+        let mut reply = vec![0; size as usize];
+        self.stream.read_exact(&mut reply)?;
+
+        // dwm terminates the JSON with a NUL byte
+        let reply = reply.strip_suffix(&[0]).unwrap_or(&reply);
+        serde_json::from_slice(reply).map_err(|err| invalid_data(&err.to_string()))
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Polls dwm in a background thread and calls `on_change` whenever the state
+/// changes, with `None` while dwm can't be reached. The thread stops once
+/// `on_change` returns false.
+pub fn watch(path: PathBuf, on_change: impl Fn(Option<DwmState>) -> bool + Send + 'static) {
+    thread::spawn(move || {
+        let mut client = None;
+        let mut last = None;
+        loop {
+            if client.is_none() {
+                client = IpcClient::connect(&path).ok();
+            }
+            let state = client.as_mut().and_then(|c| c.state().ok());
+            if state.is_none() {
+                // Reconnect next time, dwm may have restarted
+                client = None;
+            }
+            if state != last {
+                if !on_change(state.clone()) {
+                    return;
+                }
+                last = state;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+/// Runs the binding for `combo` in dwm and prints what was sent.
+pub fn run_binding(config: &DwmConfig, combo: &str) -> io::Result<()> {
+    let key = config.find_key(combo).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, format!("no binding for '{}'", combo))
+    })?;
+    let sent = IpcClient::connect(&socket_path(config))?.run_command(&key.action)?;
+    println!("{}", sent);
+    Ok(())
+}
+
+/// Prints the live state for scripts and status bars.
+pub fn print_state(config: &DwmConfig) -> io::Result<()> {
+    let state = IpcClient::connect(&socket_path(config))?.state()?;
+    println!("monitor: {}", state.monitor);
+    println!("tags: {}", state.tags.join(" "));
+    println!("layout: {}", state.layout);
+    match &state.focused {
+        Some(client) => {
+            println!("focused: {}", client.name);
+            println!("floating: {}", client.floating);
+            println!("fullscreen: {}", client.fullscreen);
+        }
+        None => println!("focused:"),
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct Monitor {
+    num: i32,
+    is_selected: bool,
+    monitor_geometry: Geometry,
+    tagset: TagSet,
+    layout: MonitorLayout,
+    clients: MonitorClients,
+}
+
+#[derive(Deserialize)]
+struct TagSet {
+    current: u32,
+}
+
+#[derive(Deserialize)]
+struct MonitorLayout {
+    symbol: LayoutSymbol,
+}
+
+#[derive(Deserialize)]
+struct LayoutSymbol {
+    current: String,
+}
+
+#[derive(Deserialize)]
+struct MonitorClients {
+    selected: u64,
+}
+
+#[derive(Deserialize)]
+struct Tag {
+    bit_mask: u32,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct DwmClient {
+    name: String,
+    states: ClientStates,
+}
+
+#[derive(Deserialize)]
+struct ClientStates {
+    is_floating: bool,
+    is_fullscreen: bool,
+}
+
+#[derive(Deserialize)]
+struct IpcLayout {
+    address: u64,
+}
+
+#[derive(Deserialize)]
+struct CommandResult {
+    result: String,
+    reason: Option<String>,
+}
@@ -1,3 +1,5 @@
+use crate::config::{Action, DwmConfig, Key};
+use crate::describe;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,6 +9,49 @@ pub struct Keybinding {
     pub function: String,
     pub description: String,
     pub category: Category,
+    /// What dwm runs for this combo, when found in the config.
+    #[serde(default)]
+    pub action: Option<Action>,
+}
+
+impl Keybinding {
+    /// The key combination as typed, e.g. `Super+Shift+Return`.
+    pub fn combo(&self) -> String {
+        self.modifiers
+            .iter()
+            .map(String::as_str)
+            .chain((!self.key.is_empty()).then_some(self.key.as_str()))
+            .collect::<Vec<_>>()
+            .join("+")
+    }
+
+    /// Whether the binding is only in a comment or `#if 0`-style dead branch of the config.
+    pub fn is_disabled(&self) -> bool {
+        self.action.as_ref().is_some_and(|action| action.disabled)
+    }
+
+    /// Whether this binding is triggered by `modifiers` + `keysym`, ignoring
+    /// modifier order and letter case.
+    pub fn matches(&self, modifiers: &[String], keysym: &str) -> bool {
+        self.key.eq_ignore_ascii_case(keysym)
+            && self.modifiers.len() == modifiers.len()
+            && self
+                .modifiers
+                .iter()
+                .all(|m| modifiers.iter().any(|o| o.eq_ignore_ascii_case(m)))
+    }
+
+    /// Whether this is a row standing for a range of keys, like `1-9` for the
+    /// tag keys, that includes `modifiers` + `keysym`.
+    fn covers(&self, modifiers: &[String], keysym: &str) -> bool {
+        let Some((first, last)) = self.key.split_once('-') else {
+            return false;
+        };
+        let range = first.len() == 1 && last.len() == 1 && keysym.len() == 1 && (first..=last).contains(&keysym);
+        let mut modifiers_only = self.clone();
+        modifiers_only.key = keysym.to_string();
+        range && modifiers_only.matches(modifiers, keysym)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -24,6 +69,20 @@ pub enum Category {
 }
 
 impl Category {
+    /// All categories in display order.
+    pub const ALL: [Category; 10] = [
+        Category::Media,
+        Category::Screenshot,
+        Category::Applications,
+        Category::WindowManagement,
+        Category::Navigation,
+        Category::Layout,
+        Category::Gaps,
+        Category::Tags,
+        Category::System,
+        Category::Borders,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Category::Media => "Media Controls",
@@ -39,18 +98,41 @@ impl Category {
         }
     }
 
-    pub fn color(&self) -> &'static str {
+    /// Short name used on the command line, e.g. `window-management`.
+    pub fn name(&self) -> &'static str {
         match self {
-            Category::Media => "#f38ba8",          // Red
-            Category::Screenshot => "#fab387",      // Orange
-            Category::Applications => "#f9e2af",    // Yellow
-            Category::WindowManagement => "#a6e3a1", // Green
-            Category::Layout => "#94e2d5",          // Teal
-            Category::Gaps => "#89b4fa",            // Blue
-            Category::Navigation => "#cba6f7",      // Purple
-            Category::Tags => "#f5c2e7",           // Pink
-            Category::System => "#eba0ac",          // Maroon
-            Category::Borders => "#9399b2",         // Overlay1
+            Category::Media => "media",
+            Category::Screenshot => "screenshots",
+            Category::Applications => "applications",
+            Category::WindowManagement => "window-management",
+            Category::Layout => "layout",
+            Category::Gaps => "gaps",
+            Category::Navigation => "navigation",
+            Category::Tags => "tags",
+            Category::System => "system",
+            Category::Borders => "borders",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Category> {
+        Category::ALL
+            .into_iter()
+            .find(|category| category.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Accent colour used for this category's card in every rendering.
+    pub fn rgb(&self) -> [u8; 3] {
+        match self {
+            Category::Media => [243, 139, 168],          // Red
+            Category::Screenshot => [250, 179, 135],     // Orange
+            Category::Applications => [249, 226, 175],   // Yellow
+            Category::WindowManagement => [166, 227, 161], // Green
+            Category::Layout => [148, 226, 213],         // Teal
+            Category::Gaps => [137, 180, 250],           // Blue
+            Category::Navigation => [203, 166, 247],     // Purple
+            Category::Tags => [245, 194, 231],           // Pink
+            Category::System => [235, 160, 172],         // Maroon
+            Category::Borders => [180, 190, 254],        // Lavender
         }
     }
 }
@@ -64,6 +146,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Volume Down".to_string(),
             description: "Decrease volume by 5%".to_string(),
             category: Category::Media,
+            action: None,
         },
         Keybinding {
             modifiers: vec![],
@@ -71,6 +154,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Volume Up".to_string(),
             description: "Increase volume by 5%".to_string(),
             category: Category::Media,
+            action: None,
         },
         Keybinding {
             modifiers: vec![],
@@ -78,6 +162,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Mute Toggle".to_string(),
             description: "Toggle audio mute".to_string(),
             category: Category::Media,
+            action: None,
         },
         Keybinding {
             modifiers: vec![],
@@ -85,6 +170,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Brightness Up".to_string(),
             description: "Increase screen brightness".to_string(),
             category: Category::Media,
+            action: None,
         },
         Keybinding {
             modifiers: vec![],
@@ -92,6 +178,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Brightness Down".to_string(),
             description: "Decrease screen brightness".to_string(),
             category: Category::Media,
+            action: None,
         },
         Keybinding {
             modifiers: vec![],
@@ -99,6 +186,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Play/Pause".to_string(),
             description: "Toggle media playback".to_string(),
             category: Category::Media,
+            action: None,
         },
         Keybinding {
             modifiers: vec![],
@@ -106,6 +194,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Next Track".to_string(),
             description: "Skip to next track".to_string(),
             category: Category::Media,
+            action: None,
         },
         Keybinding {
             modifiers: vec![],
@@ -113,6 +202,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Previous Track".to_string(),
             description: "Go to previous track".to_string(),
             category: Category::Media,
+            action: None,
         },
 
         // Screenshots
@@ -122,6 +212,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Screenshot (Full)".to_string(),
             description: "Take fullscreen screenshot to clipboard".to_string(),
             category: Category::Screenshot,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -129,6 +220,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Screenshot (Select)".to_string(),
             description: "Take selected area screenshot to clipboard".to_string(),
             category: Category::Screenshot,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Alt".to_string()],
@@ -136,6 +228,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Screenshot Menu".to_string(),
             description: "Open screenshot utility menu".to_string(),
             category: Category::Screenshot,
+            action: None,
         },
 
         // Applications
@@ -145,6 +238,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Browser".to_string(),
             description: "Launch Firefox browser".to_string(),
             category: Category::Applications,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -152,6 +246,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "File Manager".to_string(),
             description: "Launch PCManFM file manager".to_string(),
             category: Category::Applications,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -159,6 +254,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Code Editor".to_string(),
             description: "Launch VS Code".to_string(),
             category: Category::Applications,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -166,6 +262,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Terminal".to_string(),
             description: "Launch Kitty terminal".to_string(),
             category: Category::Applications,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -173,6 +270,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "App Launcher".to_string(),
             description: "Open Rofi application launcher".to_string(),
             category: Category::Applications,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Alt".to_string()],
@@ -180,6 +278,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "EWW Widget".to_string(),
             description: "Open EWW widget".to_string(),
             category: Category::Applications,
+            action: None,
         },
 
         // Window Management
@@ -189,6 +288,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Kill Window".to_string(),
             description: "Close the focused window".to_string(),
             category: Category::WindowManagement,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -196,6 +296,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Toggle Floating".to_string(),
             description: "Toggle floating mode for focused window".to_string(),
             category: Category::WindowManagement,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -203,6 +304,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Toggle Fullscreen".to_string(),
             description: "Toggle fullscreen for focused window".to_string(),
             category: Category::WindowManagement,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -210,6 +312,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Hide Window".to_string(),
             description: "Hide the focused window".to_string(),
             category: Category::WindowManagement,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -217,6 +320,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Restore Window".to_string(),
             description: "Restore hidden window".to_string(),
             category: Category::WindowManagement,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -224,6 +328,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Zoom Master".to_string(),
             description: "Move window to/from master area".to_string(),
             category: Category::WindowManagement,
+            action: None,
         },
 
         // Navigation
@@ -233,6 +338,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Focus Next".to_string(),
             description: "Focus next window in stack".to_string(),
             category: Category::Navigation,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -240,6 +346,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Focus Previous".to_string(),
             description: "Focus previous window in stack".to_string(),
             category: Category::Navigation,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -247,6 +354,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Move Down".to_string(),
             description: "Move window down in stack".to_string(),
             category: Category::Navigation,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -254,6 +362,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Move Up".to_string(),
             description: "Move window up in stack".to_string(),
             category: Category::Navigation,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -261,6 +370,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Previous Tag".to_string(),
             description: "Switch to previous tag".to_string(),
             category: Category::Navigation,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -268,6 +378,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Next Tag".to_string(),
             description: "Switch to next tag".to_string(),
             category: Category::Navigation,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -275,6 +386,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Last Tag".to_string(),
             description: "Switch to last viewed tag".to_string(),
             category: Category::Navigation,
+            action: None,
         },
 
         // Layout Management
@@ -284,6 +396,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Shrink Master".to_string(),
             description: "Decrease master area size".to_string(),
             category: Category::Layout,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -291,6 +404,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Expand Master".to_string(),
             description: "Increase master area size".to_string(),
             category: Category::Layout,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -298,6 +412,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Inc Master".to_string(),
             description: "Increase number of masters".to_string(),
             category: Category::Layout,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -305,6 +420,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Dwindle Layout".to_string(),
             description: "Set layout to dwindle".to_string(),
             category: Category::Layout,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -312,6 +428,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Grid Layout".to_string(),
             description: "Set layout to grid".to_string(),
             category: Category::Layout,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -319,6 +436,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Cycle Layout".to_string(),
             description: "Cycle through layouts".to_string(),
             category: Category::Layout,
+            action: None,
         },
 
         // Gap Management
@@ -328,6 +446,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Toggle Gaps".to_string(),
             description: "Toggle gaps on/off".to_string(),
             category: Category::Gaps,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Ctrl".to_string()],
@@ -335,6 +454,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Increase All Gaps".to_string(),
             description: "Increase all gaps".to_string(),
             category: Category::Gaps,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Ctrl".to_string()],
@@ -342,6 +462,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Decrease All Gaps".to_string(),
             description: "Decrease all gaps".to_string(),
             category: Category::Gaps,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Ctrl".to_string(), "Shift".to_string()],
@@ -349,6 +470,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Default Gaps".to_string(),
             description: "Reset gaps to default".to_string(),
             category: Category::Gaps,
+            action: None,
         },
 
         // Tags (1-9)
@@ -358,6 +480,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "View Tag".to_string(),
             description: "Switch to tag 1-9".to_string(),
             category: Category::Tags,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -365,6 +488,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Move to Tag".to_string(),
             description: "Move window to tag 1-9".to_string(),
             category: Category::Tags,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Ctrl".to_string()],
@@ -372,6 +496,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Toggle Tag View".to_string(),
             description: "Toggle view of tag 1-9".to_string(),
             category: Category::Tags,
+            action: None,
         },
 
         // System
@@ -381,6 +506,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Quit DWM".to_string(),
             description: "Kill dwm and bar".to_string(),
             category: Category::System,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -388,6 +514,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Restart DWM".to_string(),
             description: "Restart dwm".to_string(),
             category: Category::System,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Alt".to_string()],
@@ -395,6 +522,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Lock Screen".to_string(),
             description: "Lock the screen".to_string(),
             category: Category::System,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Alt".to_string()],
@@ -402,6 +530,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Power Menu".to_string(),
             description: "Open power menu".to_string(),
             category: Category::System,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string()],
@@ -409,6 +538,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Keyboard Layout".to_string(),
             description: "Switch keyboard layout".to_string(),
             category: Category::System,
+            action: None,
         },
 
         // Borders
@@ -418,6 +548,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Decrease Border".to_string(),
             description: "Decrease border width".to_string(),
             category: Category::Borders,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -425,6 +556,7 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Increase Border".to_string(),
             description: "Increase border width".to_string(),
             category: Category::Borders,
+            action: None,
         },
         Keybinding {
             modifiers: vec!["Super".to_string(), "Shift".to_string()],
@@ -432,6 +564,164 @@ pub fn get_keybindings() -> Vec<Keybinding> {
             function: "Default Border".to_string(),
             description: "Reset border to default".to_string(),
             category: Category::Borders,
+            action: None,
         },
     ]
 }
+
+/// The dwm function each built-in row stands for and, for `spawn` rows whose
+/// text names a program, a word its command contains.
+const BUILTIN_ACTIONS: [(&str, &str, &str); 51] = [
+    ("Volume Down", "spawn", ""),
+    ("Volume Up", "spawn", ""),
+    ("Mute Toggle", "spawn", ""),
+    ("Brightness Up", "spawn", ""),
+    ("Brightness Down", "spawn", ""),
+    ("Play/Pause", "spawn", ""),
+    ("Next Track", "spawn", ""),
+    ("Previous Track", "spawn", ""),
+    ("Screenshot (Full)", "spawn", ""),
+    ("Screenshot (Select)", "spawn", ""),
+    ("Screenshot Menu", "spawn", "maim"),
+    ("Browser", "spawn", "firefox"),
+    ("File Manager", "spawn", "pcmanfm"),
+    ("Code Editor", "spawn", "code"),
+    ("Terminal", "spawn", "kitty"),
+    ("App Launcher", "spawn", "rofi"),
+    ("EWW Widget", "spawn", "eww"),
+    ("Kill Window", "killclient", ""),
+    ("Toggle Floating", "togglefloating", ""),
+    ("Toggle Fullscreen", "togglefullscr", ""),
+    ("Hide Window", "hidewin", ""),
+    ("Restore Window", "restorewin", ""),
+    ("Zoom Master", "zoom", ""),
+    ("Focus Next", "focusstack", ""),
+    ("Focus Previous", "focusstack", ""),
+    ("Move Down", "movestack", ""),
+    ("Move Up", "movestack", ""),
+    ("Previous Tag", "shiftview", ""),
+    ("Next Tag", "shiftview", ""),
+    ("Last Tag", "view", ""),
+    ("Shrink Master", "setmfact", ""),
+    ("Expand Master", "setmfact", ""),
+    ("Inc Master", "incnmaster", ""),
+    ("Dwindle Layout", "setlayout", ""),
+    ("Grid Layout", "setlayout", ""),
+    ("Cycle Layout", "cyclelayout", ""),
+    ("Toggle Gaps", "togglegaps", ""),
+    ("Increase All Gaps", "incrgaps", ""),
+    ("Decrease All Gaps", "incrgaps", ""),
+    ("Default Gaps", "defaultgaps", ""),
+    ("View Tag", "view", ""),
+    ("Move to Tag", "tag", ""),
+    ("Toggle Tag View", "toggleview", ""),
+    ("Quit DWM", "spawn", "killall"),
+    ("Restart DWM", "restart", ""),
+    ("Lock Screen", "spawn", "lock"),
+    ("Power Menu", "spawn", "powermenu"),
+    ("Keyboard Layout", "spawn", "kblayout"),
+    ("Decrease Border", "setborderpx", ""),
+    ("Increase Border", "setborderpx", ""),
+    ("Default Border", "setborderpx", ""),
+];
+
+impl Keybinding {
+    /// Whether this built-in row's name and description fit `action`: it calls the
+    /// function the row stands for and, for a program launcher, runs that program.
+    fn describes(&self, action: &Action) -> bool {
+        BUILTIN_ACTIONS
+            .iter()
+            .find(|(name, _, _)| *name == self.function)
+            .is_some_and(|(_, function, program)| {
+                action.function == *function
+                    && (program.is_empty()
+                        || action
+                            .command()
+                            .is_some_and(|argv| argv.iter().any(|word| word.contains(program))))
+            })
+    }
+}
+
+/// The built-in keybindings with their actions looked up in `config`, followed
+/// by the config's other bindings, described from their function and argument.
+///
+/// A built-in row keeps its name and description only for a config entry that
+/// does what it says; any other entry on that combo is described on its own.
+/// With a config file loaded, built-in rows it doesn't bind are left out.
+/// Each config entry is attached to at most one binding, in file order, so combos
+/// bound twice keep both actions apart.
+pub fn load_keybindings(config: &DwmConfig) -> Vec<Keybinding> {
+    let mut keybindings = get_keybindings();
+    // Tag keys the built-in `1-9` rows already stand for
+    let mut used: Vec<bool> = config
+        .keys
+        .iter()
+        .map(|key| {
+            keybindings
+                .iter()
+                .any(|kb| kb.covers(&key.modifiers, &key.keysym) && kb.describes(&key.action))
+        })
+        .collect();
+
+    for keybinding in &mut keybindings {
+        let found = config.keys.iter().enumerate().find(|(i, key)| {
+            !used[*i] && keybinding.matches(&key.modifiers, &key.keysym) && keybinding.describes(&key.action)
+        });
+        if let Some((i, key)) = found {
+            used[i] = true;
+            keybinding.action = Some(key.action.clone());
+        }
+    }
+
+    if config.path.is_some() {
+        keybindings.retain(|kb| {
+            kb.action.is_some()
+                || config
+                    .keys
+                    .iter()
+                    .any(|key| kb.covers(&key.modifiers, &key.keysym) && kb.describes(&key.action))
+        });
+    }
+
+    for (key, _) in config.keys.iter().zip(used).filter(|(_, used)| !used) {
+        keybindings.push(described(key, config));
+    }
+
+    keybindings
+}
+
+/// One binding per entry of the config's `keys[]`, in file order, named and
+/// described as in [`load_keybindings`]; tag keys that a range row like `1-9`
+/// stands for there are described one by one.
+pub fn config_keybindings(config: &DwmConfig) -> Vec<Keybinding> {
+    let loaded = load_keybindings(config);
+    config
+        .keys
+        .iter()
+        .map(|key| {
+            loaded
+                .iter()
+                .find(|kb| kb.action.as_ref() == Some(&key.action))
+                .cloned()
+                .unwrap_or_else(|| described(key, config))
+        })
+        .collect()
+}
+
+/// The config's commented-out and `#if 0` bindings, described like the
+/// config-only ones.
+pub fn disabled_keybindings(config: &DwmConfig) -> Vec<Keybinding> {
+    config.disabled_keys.iter().map(|key| described(key, config)).collect()
+}
+
+fn described(key: &Key, config: &DwmConfig) -> Keybinding {
+    let (function, description) = describe::describe(&key.action, &config.layouts);
+    Keybinding {
+        modifiers: key.modifiers.clone(),
+        key: key.keysym.clone(),
+        function,
+        description,
+        category: describe::category(&key.action, &key.keysym),
+        action: Some(key.action.clone()),
+    }
+}
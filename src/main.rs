@@ -1,26 +1,218 @@
+mod actions;
+mod cli;
+mod compare;
+mod config;
+mod daemon;
+mod deps;
+mod describe;
+mod groups;
+mod ipc;
 mod keybindings;
+mod menu;
+mod monitor;
+mod pairs;
+mod pdf;
+mod preview;
 mod ui;
+mod wallpaper;
+mod window;
+mod xtest;
 
+use cli::Command;
 use eframe::egui;
-use ui::CheatsheetApp;
+use keybindings::{load_keybindings, Keybinding};
+use ui::{CheatsheetApp, Startup};
 
-fn main() -> Result<(), eframe::Error> {
+fn main() {
     env_logger::init();
-    
+
+    let cli = match cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("dwm-cheatsheet: {}\n\n{}", err, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(err) = run(cli) {
+        eprintln!("dwm-cheatsheet: {}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: cli::Cli) -> Result<(), Box<dyn std::error::Error>> {
+    match &cli.command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        Command::SendKeys { combo, delay } => return xtest::send_keys(combo, *delay),
+        // Reads its own two configs instead of --config
+        Command::Diff(options) => return diff(options),
+        // A running daemon answers before anything is parsed
+        Command::Gui(options) | Command::Show(options) | Command::Toggle(options) => {
+            let request = match &cli.command {
+                Command::Toggle(_) => daemon::Request::Toggle(options.view.clone()),
+                _ => daemon::Request::Show(options.view.clone()),
+            };
+            let mut startup = options.startup_flags();
+            if let Some(path) = &cli.config {
+                startup.push(config_flag(&config::resolve(path)?));
+            }
+            if daemon::send(&request, &startup)? {
+                return Ok(());
+            }
+        }
+        _ => {}
+    }
+
+    let config = config::load(cli.config.as_deref())?;
+    let keybindings = load_keybindings(&config);
+
+    match cli.command {
+        Command::Gui(options) => run_gui(
+            keybindings,
+            config,
+            Startup {
+                daemon: None,
+                visible: true,
+                options,
+                diff: None,
+            },
+        )?,
+        Command::Daemon(options) => {
+            let listener = daemon::Listener::bind(daemon_flags(&options, &config))?;
+            run_gui(
+                keybindings,
+                config,
+                Startup {
+                    daemon: Some(listener),
+                    visible: false,
+                    options,
+                    diff: None,
+                },
+            )?
+        }
+        // No daemon was running, so this one becomes it
+        Command::Toggle(options) | Command::Show(options) => {
+            let listener = daemon::Listener::bind(daemon_flags(&options, &config))?;
+            run_gui(
+                keybindings,
+                config,
+                Startup {
+                    daemon: Some(listener),
+                    visible: true,
+                    options,
+                    diff: None,
+                },
+            )?
+        }
+        Command::Pdf(options) => {
+            pdf::export(&keybindings, &options)?;
+            println!("Wrote {}", options.output.display());
+        }
+        Command::Wallpaper(options) => {
+            for path in wallpaper::export(&keybindings, &options)? {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Command::Dmenu { exec: false } => menu::print_dmenu(&keybindings)?,
+        Command::Dmenu { exec: true } => menu::exec_dmenu_selection(&keybindings)?,
+        Command::Rofi => menu::rofi(&keybindings)?,
+        Command::RunInDwm { combo } => ipc::run_binding(&config, &combo)?,
+        Command::Edit { combo } => {
+            let key = config.find_key(&combo).ok_or_else(|| format!("no binding for '{}'", combo))?;
+            let file = key.action.file.as_deref().ok_or("the bundled config has no file to edit; pass --config")?;
+            actions::edit_from_terminal(file, Some(key.action.line))?;
+        }
+        Command::State => ipc::print_state(&config)?,
+        Command::Deps => deps::print_missing(&keybindings)?,
+        Command::Help | Command::SendKeys { .. } | Command::Diff(_) => unreachable!("handled above"),
+    }
+
+    Ok(())
+}
+
+fn diff(options: &compare::DiffOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let diff = compare::diff(options)?;
+    if !options.gui {
+        return Ok(compare::print(&diff, options.format)?);
+    }
+    let config = compare::read(&options.new)?;
+    run_gui(
+        load_keybindings(&config),
+        config,
+        Startup {
+            daemon: None,
+            visible: true,
+            options: Default::default(),
+            diff: Some(diff),
+        },
+    )
+}
+
+/// Flags a running daemon accepts forwarded requests with: its own startup
+/// options and config file. `--reset-state` is left out since only a new
+/// instance can act on it.
+fn daemon_flags(options: &ui::WindowOptions, config: &config::DwmConfig) -> Vec<String> {
+    let mut flags = options.startup_flags();
+    flags.retain(|flag| flag != "--reset-state");
+    flags.extend(config.path.as_deref().map(config_flag));
+    flags
+}
+
+/// `--config` for a config file, written the same however the path was given.
+fn config_flag(path: &std::path::Path) -> String {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    format!("--config {}", path.display())
+}
+
+fn run_gui(
+    keybindings: Vec<Keybinding>,
+    config: config::DwmConfig,
+    startup: Startup,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let window = &startup.options;
+    if window.reset_state {
+        ui::reset_saved_state(window.class())?;
+    }
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_title("DWM Keybinding Cheatsheet")
+        .with_app_id(window.class())
+        .with_visible(startup.visible);
+
+    let placement = window.placement(&ipc::socket_path(&config));
+    let (width, height) = placement
+        .map(|(_, size)| size)
+        .or(window.geometry.and_then(|geometry| geometry.size))
+        .unwrap_or(ui::DEFAULT_SIZE);
+    viewport = viewport.with_inner_size([width as f32, height as f32]);
+    if let Some(((x, y), _)) = placement {
+        viewport = viewport.with_position([x as f32, y as f32]);
+    }
+
+    if window.overlay {
+        viewport = viewport
+            .with_decorations(false)
+            .with_always_on_top()
+            .with_transparent(true);
+    } else {
+        viewport = viewport.with_min_inner_size([800.0, 600.0]);
+    }
+
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1200.0, 800.0])
-            .with_title("DWM Keybinding Cheatsheet")
-            .with_min_inner_size([800.0, 600.0]),
+        viewport,
+        persist_window: window.remembers_placement(),
         ..Default::default()
     };
-    
+
     eframe::run_native(
         "DWM Cheatsheet",
         options,
         Box::new(|cc| {
             // Configure custom fonts and styles here if needed
-            Ok(Box::new(CheatsheetApp::new(cc)))
+            Ok(Box::new(CheatsheetApp::new(cc, keybindings, config, startup)))
         }),
-    )
+    )?;
+    Ok(())
 }
@@ -0,0 +1,102 @@
+use crate::actions;
+use crate::keybindings::Keybinding;
+use std::io::{self, BufRead, Write};
+
+/// Separates rofi's row options, see rofi-script(5).
+const FIELD_SEPARATOR: char = '\x1f';
+
+/// rofi's `ROFI_RETV` for a selected entry and for `kb-custom-1`.
+const RETV_SELECTED: &str = "1";
+const RETV_CUSTOM_1: &str = "10";
+
+/// One line per binding, suitable for piping into dmenu or `rofi -dmenu`.
+pub fn line(keybinding: &Keybinding) -> String {
+    format!(
+        "{:<28} {} — {}",
+        keybinding.combo(),
+        keybinding.function,
+        keybinding.description
+    )
+}
+
+/// Prints every binding as a dmenu line.
+pub fn print_dmenu(keybindings: &[Keybinding]) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    for keybinding in keybindings {
+        writeln!(stdout, "{}", line(keybinding))?;
+    }
+    Ok(())
+}
+
+/// Reads the line dmenu printed and performs its binding.
+pub fn exec_dmenu_selection(keybindings: &[Keybinding]) -> io::Result<()> {
+    let mut selection = String::new();
+    io::stdin().lock().read_line(&mut selection)?;
+    let selection = selection.trim_end_matches('\n');
+    if selection.is_empty() {
+        return Ok(());
+    }
+
+    match keybindings.iter().find(|kb| line(kb) == selection) {
+        Some(keybinding) => activate(keybinding),
+        None => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no binding matches '{}'", selection),
+        )),
+    }
+}
+
+/// Implements rofi's script mode: rofi runs us once to list entries and again
+/// with `ROFI_RETV`/`ROFI_INFO` set once the user picks one.
+///
+/// Enter runs `spawn` bindings and copies the combo of everything else;
+/// `kb-custom-1` (Alt+1 by default) always copies.
+pub fn rofi(keybindings: &[Keybinding]) -> io::Result<()> {
+    let retv = std::env::var("ROFI_RETV").unwrap_or_default();
+    let selected = std::env::var("ROFI_INFO")
+        .ok()
+        .and_then(|info| info.parse::<usize>().ok())
+        .and_then(|index| keybindings.get(index));
+
+    match (retv.as_str(), selected) {
+        (RETV_SELECTED, Some(keybinding)) => activate(keybinding),
+        (RETV_CUSTOM_1, Some(keybinding)) => actions::copy_to_clipboard(&keybinding.combo()),
+        _ => print_rofi_entries(keybindings),
+    }
+}
+
+fn print_rofi_entries(keybindings: &[Keybinding]) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "\0prompt{}keys", FIELD_SEPARATOR)?;
+    writeln!(stdout, "\0no-custom{}true", FIELD_SEPARATOR)?;
+    writeln!(
+        stdout,
+        "\0message{}Enter: run or copy · Alt+1: copy combo",
+        FIELD_SEPARATOR
+    )?;
+
+    for (index, keybinding) in keybindings.iter().enumerate() {
+        let mut meta = vec![keybinding.category.as_str().to_string()];
+        if let Some(action) = &keybinding.action {
+            meta.push(action.function.clone());
+            meta.extend(action.shell_command());
+        }
+        writeln!(
+            stdout,
+            "{}\0info{sep}{}{sep}meta{sep}{}",
+            line(keybinding),
+            index,
+            meta.join(" ").replace('\n', " "),
+            sep = FIELD_SEPARATOR
+        )?;
+    }
+    Ok(())
+}
+
+/// Runs a `spawn` binding's command, otherwise copies the combo.
+fn activate(keybinding: &Keybinding) -> io::Result<()> {
+    match keybinding.action.as_ref().and_then(|action| action.command()) {
+        Some(argv) => actions::spawn(argv),
+        None => actions::copy_to_clipboard(&keybinding.combo()),
+    }
+}
@@ -0,0 +1,272 @@
+use crate::ipc::IpcClient;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use x11rb::connection::Connection;
+use x11rb::protocol::randr::ConnectionExt as _;
+use x11rb::protocol::xproto::ConnectionExt as _;
+
+/// A monitor's area in screen pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Geometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Geometry {
+    fn contains(&self, x: i32, y: i32) -> bool {
+        (self.x..self.x + self.width as i32).contains(&x)
+            && (self.y..self.y + self.height as i32).contains(&y)
+    }
+}
+
+/// An X11-style `WxH+X+Y` window geometry. Either part may be left out.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WindowGeometry {
+    pub size: Option<(u32, u32)>,
+    pub offset: Option<(Offset, Offset)>,
+}
+
+/// `+N` counts from the left or top edge, `-N` from the right or bottom edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Offset {
+    Start(i32),
+    End(i32),
+}
+
+impl Offset {
+    fn parse(spec: &str) -> Option<Offset> {
+        match spec.split_at_checked(1)? {
+            ("+", n) => Some(Offset::Start(distance(n)?)),
+            ("-", n) => Some(Offset::End(distance(n)?)),
+            _ => None,
+        }
+    }
+
+    /// Start coordinate of a window `size` long within `start..start + extent`.
+    fn resolve(self, start: i32, extent: u32, size: u32) -> i32 {
+        match self {
+            Offset::Start(n) => start.saturating_add(n),
+            Offset::End(n) => start
+                .saturating_add(extent as i32)
+                .saturating_sub(size as i32)
+                .saturating_sub(n),
+        }
+    }
+}
+
+/// A non-negative pixel count that fits the X11 coordinate arithmetic.
+fn distance(n: &str) -> Option<i32> {
+    // `parse` alone would accept a leading `+`
+    if !n.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    n.parse().ok()
+}
+
+impl fmt::Display for Offset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Offset::Start(n) => write!(f, "+{}", n),
+            Offset::End(n) => write!(f, "-{}", n),
+        }
+    }
+}
+
+/// As `--geometry` takes it, e.g. `800x600-0+0`.
+impl fmt::Display for WindowGeometry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some((width, height)) = self.size {
+            write!(f, "{}x{}", width, height)?;
+        }
+        if let Some((x, y)) = self.offset {
+            write!(f, "{}{}", x, y)?;
+        }
+        Ok(())
+    }
+}
+
+impl WindowGeometry {
+    pub fn parse(spec: &str) -> Option<WindowGeometry> {
+        let offset_start = spec.find(['+', '-']).unwrap_or(spec.len());
+        let (size, offset) = spec.split_at(offset_start);
+
+        let size = match size {
+            "" => None,
+            size => {
+                let (w, h) = size.split_once(['x', 'X'])?;
+                let positive = |n: &str| distance(n).filter(|&n| n > 0).map(|n| n as u32);
+                Some((positive(w)?, positive(h)?))
+            }
+        };
+
+        let offset = match offset {
+            "" => None,
+            offset => {
+                // The second offset starts at the next sign
+                let (x, y) = offset.split_at(offset[1..].find(['+', '-'])? + 1);
+                Some((Offset::parse(x)?, Offset::parse(y)?))
+            }
+        };
+
+        (size.is_some() || offset.is_some()).then_some(WindowGeometry { size, offset })
+    }
+
+    /// Position and size of a window placed on `area`, using `default_size` when
+    /// no size was given and centring it when no offset was.
+    pub fn place(&self, area: Geometry, default_size: (u32, u32)) -> ((i32, i32), (u32, u32)) {
+        let (width, height) = self.size.unwrap_or(default_size);
+        let position = match self.offset {
+            Some((x, y)) => (
+                x.resolve(area.x, area.width, width),
+                y.resolve(area.y, area.height, height),
+            ),
+            None => (
+                area.x + (area.width as i32 - width as i32) / 2,
+                area.y + (area.height as i32 - height as i32) / 2,
+            ),
+        };
+        (position, (width, height))
+    }
+}
+
+/// The monitor the user is working on: dwm's selected monitor when the IPC patch
+/// answers, otherwise the one under the pointer.
+pub fn focused(ipc_socket: &Path) -> Option<Geometry> {
+    let from_dwm = IpcClient::connect(ipc_socket).and_then(|mut client| client.selected_monitor());
+    match from_dwm {
+        Ok(geometry) => Some(geometry),
+        Err(_) => {
+            let screen = X11Screen::query().ok()?;
+            let (x, y) = screen.pointer;
+            Some(
+                screen
+                    .monitors
+                    .into_iter()
+                    .find(|monitor| monitor.contains(x, y))
+                    .unwrap_or(screen.area),
+            )
+        }
+    }
+}
+
+/// Monitor `index` in dwm's numbering, which follows Xinerama and RandR order.
+pub fn nth(ipc_socket: &Path, index: usize) -> Option<Geometry> {
+    let from_dwm = IpcClient::connect(ipc_socket).and_then(|mut client| client.monitors());
+    match from_dwm {
+        Ok(monitors) => monitors.get(index).copied(),
+        Err(_) => X11Screen::query().ok()?.monitors.get(index).copied(),
+    }
+}
+
+/// Monitor `index` if one is asked for, the focused monitor otherwise.
+pub fn target(ipc_socket: &Path, index: Option<usize>) -> Option<Geometry> {
+    match index {
+        Some(index) => nth(ipc_socket, index),
+        None => focused(ipc_socket),
+    }
+}
+
+/// The whole X screen, spanning every monitor.
+pub fn screen() -> Option<Geometry> {
+    X11Screen::query().ok().map(|screen| screen.area)
+}
+
+struct X11Screen {
+    area: Geometry,
+    /// RandR monitors, empty without RandR 1.5.
+    monitors: Vec<Geometry>,
+    pointer: (i32, i32),
+}
+
+impl X11Screen {
+    fn query() -> Result<X11Screen, Box<dyn Error>> {
+        let (conn, screen) = x11rb::connect(None)?;
+        let screen = &conn.setup().roots[screen];
+        let pointer = conn.query_pointer(screen.root)?.reply()?;
+
+        let monitors = conn
+            .randr_get_monitors(screen.root, true)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map_or_else(Vec::new, |reply| reply.monitors)
+            .iter()
+            .map(|m| Geometry {
+                x: i32::from(m.x),
+                y: i32::from(m.y),
+                width: u32::from(m.width),
+                height: u32::from(m.height),
+            })
+            .collect();
+
+        Ok(X11Screen {
+            area: Geometry {
+                x: 0,
+                y: 0,
+                width: u32::from(screen.width_in_pixels),
+                height: u32::from(screen.height_in_pixels),
+            },
+            monitors,
+            pointer: (i32::from(pointer.root_x), i32::from(pointer.root_y)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: Geometry = Geometry { x: 1920, y: 0, width: 2560, height: 1440 };
+
+    #[test]
+    fn geometry_takes_a_size_an_offset_or_both() {
+        assert_eq!(
+            WindowGeometry::parse("800x600"),
+            Some(WindowGeometry { size: Some((800, 600)), offset: None })
+        );
+        assert_eq!(
+            WindowGeometry::parse("+10+20"),
+            Some(WindowGeometry { size: None, offset: Some((Offset::Start(10), Offset::Start(20))) })
+        );
+        assert_eq!(
+            WindowGeometry::parse("800X600-0-0"),
+            Some(WindowGeometry { size: Some((800, 600)), offset: Some((Offset::End(0), Offset::End(0))) })
+        );
+        assert_eq!(WindowGeometry::parse("800x600-0+5").unwrap().to_string(), "800x600-0+5");
+    }
+
+    #[test]
+    fn malformed_geometries_are_rejected() {
+        for spec in ["", "+10", "0x600", "800x0", "800", "800x", "x600", "800x600+", "+10+20+30", "++5+0", "+2147483648+0"] {
+            assert_eq!(WindowGeometry::parse(spec), None, "{:?}", spec);
+        }
+        assert_eq!(WindowGeometry::parse("2147483648x600"), None);
+    }
+
+    #[test]
+    fn place_counts_negative_offsets_from_the_far_edges() {
+        let geometry = WindowGeometry::parse("800x600-0-0").unwrap();
+        assert_eq!(geometry.place(AREA, (1, 1)), ((1920 + 2560 - 800, 1440 - 600), (800, 600)));
+        let geometry = WindowGeometry::parse("-10+20").unwrap();
+        assert_eq!(geometry.place(AREA, (400, 300)), ((1920 + 2560 - 400 - 10, 20), (400, 300)));
+        let geometry = WindowGeometry::parse("+10+20").unwrap();
+        assert_eq!(geometry.place(AREA, (400, 300)), ((1930, 20), (400, 300)));
+    }
+
+    #[test]
+    fn place_centres_without_an_offset() {
+        let geometry = WindowGeometry::parse("800x600").unwrap();
+        assert_eq!(geometry.place(AREA, (1, 1)), ((1920 + 880, 420), (800, 600)));
+    }
+
+    #[test]
+    fn huge_offsets_saturate_instead_of_wrapping() {
+        let geometry = WindowGeometry::parse("+2147483647-2147483647").unwrap();
+        let ((x, y), _) = geometry.place(AREA, (400, 300));
+        assert_eq!(x, i32::MAX);
+        assert!(y < 0);
+    }
+}
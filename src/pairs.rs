@@ -0,0 +1,163 @@
+use crate::config::{Action, Argument};
+use crate::keybindings::Keybinding;
+
+/// One row of a card: a binding of its own, or two bindings calling the same
+/// function in opposite directions, e.g. `incrgaps +1` and `incrgaps -1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Row {
+    Single(usize),
+    /// Indices of the positive and the negative binding.
+    Pair { up: usize, down: usize },
+}
+
+impl Row {
+    pub fn contains(self, index: usize) -> bool {
+        match self {
+            Row::Single(i) => i == index,
+            Row::Pair { up, down } => up == index || down == index,
+        }
+    }
+
+    /// The index the row is selected and opened by: the binding itself, or the
+    /// pair's positive one.
+    pub fn lead(self) -> usize {
+        match self {
+            Row::Single(i) => i,
+            Row::Pair { up, .. } => up,
+        }
+    }
+}
+
+/// Rows for `keybindings`, in order, each binding paired with the first later
+/// one calling the same function with the opposite argument. A pair takes the
+/// place of whichever of its bindings comes first.
+pub fn rows(keybindings: &[Keybinding]) -> Vec<Row> {
+    let mut paired = vec![false; keybindings.len()];
+    let mut rows = Vec::new();
+    for (i, keybinding) in keybindings.iter().enumerate() {
+        if paired[i] {
+            continue;
+        }
+        let partner = keybinding.action.as_ref().and_then(|action| {
+            (i + 1..keybindings.len()).find(|&j| {
+                !paired[j] && keybindings[j].action.as_ref().is_some_and(|other| opposite(action, other))
+            })
+        });
+        match partner {
+            Some(j) => {
+                paired[j] = true;
+                let (up, down) = if sign(&keybinding.action.as_ref().expect("paired binding").argument) > 0.0 {
+                    (i, j)
+                } else {
+                    (j, i)
+                };
+                rows.push(Row::Pair { up, down });
+            }
+            None => rows.push(Row::Single(i)),
+        }
+    }
+    rows
+}
+
+/// Whether `a` and `b` call the same function with arguments of opposite sign,
+/// and are both enabled or both disabled.
+fn opposite(a: &Action, b: &Action) -> bool {
+    a.function == b.function && a.disabled == b.disabled && sign(&a.argument) * sign(&b.argument) < 0.0
+}
+
+fn sign(argument: &Argument) -> f32 {
+    match argument {
+        Argument::Int(n) => n.signum() as f32,
+        Argument::Float(n) if *n != 0.0 => n.signum(),
+        _ => 0.0,
+    }
+}
+
+/// What a pair adjusts, named after its dwm function, e.g. "Inner gaps" for
+/// `incrigaps`; the function itself when it is not a known one.
+pub fn title(function: &str) -> String {
+    let title = match function {
+        "incrgaps" => "All gaps",
+        "incrigaps" => "Inner gaps",
+        "incrogaps" => "Outer gaps",
+        "incrihgaps" => "Inner horizontal gaps",
+        "incrivgaps" => "Inner vertical gaps",
+        "incrohgaps" => "Outer horizontal gaps",
+        "incrovgaps" => "Outer vertical gaps",
+        "setmfact" => "Master size",
+        "setcfact" => "Client size",
+        "incnmaster" => "Master count",
+        "focusstack" => "Focus in stack",
+        "movestack" => "Move in stack",
+        "focusmon" => "Focus monitor",
+        "tagmon" => "Send to monitor",
+        "shiftview" => "View adjacent tag",
+        _ => function,
+    };
+    title.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybindings::Category;
+
+    fn keybinding(function: &str, argument: Argument, disabled: bool) -> Keybinding {
+        Keybinding {
+            modifiers: vec!["Super".to_string()],
+            key: "a".to_string(),
+            function: function.to_string(),
+            description: String::new(),
+            category: Category::Layout,
+            action: Some(Action {
+                function: function.to_string(),
+                argument,
+                line: 1,
+                end_line: 1,
+                source: String::new(),
+                file: None,
+                disabled,
+            }),
+        }
+    }
+
+    #[test]
+    fn opposite_arguments_pair_up_positive_first() {
+        let keybindings = [
+            keybinding("incrgaps", Argument::Int(1), false),
+            keybinding("setmfact", Argument::Float(-0.05), false),
+            keybinding("incrgaps", Argument::Int(-1), false),
+            keybinding("focusmon", Argument::Int(-1), false),
+            keybinding("setmfact", Argument::Float(0.05), false),
+            keybinding("focusmon", Argument::Int(1), false),
+        ];
+        assert_eq!(
+            rows(&keybindings),
+            [Row::Pair { up: 0, down: 2 }, Row::Pair { up: 4, down: 1 }, Row::Pair { up: 5, down: 3 }]
+        );
+    }
+
+    #[test]
+    fn unrelated_or_unsigned_bindings_stay_single() {
+        let keybindings = [
+            keybinding("incrgaps", Argument::Int(1), false),
+            keybinding("incrigaps", Argument::Int(-1), false),
+            keybinding("setmfact", Argument::Float(0.05), false),
+            keybinding("setmfact", Argument::Float(0.0), false),
+            keybinding("view", Argument::UInt(1), false),
+            keybinding("view", Argument::Int(-1), false),
+        ];
+        assert_eq!(rows(&keybindings), (0..6).map(Row::Single).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn enabled_and_disabled_bindings_do_not_pair() {
+        let keybindings = [
+            keybinding("focusmon", Argument::Int(1), false),
+            keybinding("focusmon", Argument::Int(-1), true),
+            keybinding("focusmon", Argument::Int(-1), false),
+            keybinding("focusmon", Argument::Int(1), true),
+        ];
+        assert_eq!(rows(&keybindings), [Row::Pair { up: 0, down: 2 }, Row::Pair { up: 3, down: 1 }]);
+    }
+}
@@ -0,0 +1,785 @@
+use crate::keybindings::{Category, Keybinding};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Paper {
+    A4,
+    Letter,
+}
+
+impl Paper {
+    /// Portrait width and height in PDF points.
+    fn size(self) -> (f32, f32) {
+        match self {
+            Paper::A4 => (595.28, 841.89),
+            Paper::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PdfOptions {
+    pub output: PathBuf,
+    pub paper: Paper,
+    pub landscape: bool,
+    pub columns: Option<usize>,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        Self {
+            output: PathBuf::from("dwm-cheatsheet.pdf"),
+            paper: Paper::A4,
+            landscape: false,
+            columns: None,
+        }
+    }
+}
+
+// Page geometry (points)
+const MARGIN: f32 = 32.0;
+const GUTTER: f32 = 12.0;
+const CARD_GAP: f32 = 10.0;
+const CARD_RADIUS: f32 = 6.0;
+const CARD_PADDING: f32 = 8.0;
+const HEADER_HEIGHT: f32 = 20.0;
+const ROW_GAP: f32 = 6.0;
+const TITLE_HEIGHT: f32 = 34.0;
+const FOOTER_HEIGHT: f32 = 14.0;
+
+// Typography (points)
+const TITLE_SIZE: f32 = 16.0;
+const HEADER_SIZE: f32 = 10.0;
+const FUNCTION_SIZE: f32 = 8.0;
+const DESCRIPTION_SIZE: f32 = 7.0;
+const CHIP_SIZE: f32 = 7.0;
+const CHIP_HEIGHT: f32 = 11.0;
+const CHIP_PADDING: f32 = 3.0;
+const CHIP_SPACING: f32 = 2.0;
+const LINE_HEIGHT: f32 = 1.25;
+
+// Print palette: light surfaces so the sheet reads well on paper
+const TEXT: [f32; 3] = [0.12, 0.11, 0.14];
+const TEXT_VARIANT: [f32; 3] = [0.36, 0.34, 0.40];
+const OUTLINE: [f32; 3] = [0.80, 0.79, 0.82];
+const CHIP_FILL: [f32; 3] = [0.97, 0.96, 0.98];
+const CHIP_SHADOW: [f32; 3] = [0.72, 0.71, 0.75];
+const WHITE: [f32; 3] = [1.0, 1.0, 1.0];
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+    Mono,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Mono => "F3",
+        }
+    }
+
+    fn base_font(self) -> &'static str {
+        match self {
+            Font::Regular => "Helvetica",
+            Font::Bold => "Helvetica-Bold",
+            Font::Mono => "Courier",
+        }
+    }
+
+    /// Advance width of `c` in 1/1000 em, from the standard 14 font metrics.
+    fn advance(self, c: char) -> f32 {
+        let table = match self {
+            Font::Mono => return 600.0,
+            Font::Regular => &HELVETICA_WIDTHS,
+            Font::Bold => &HELVETICA_BOLD_WIDTHS,
+        };
+        match c as u32 {
+            code @ 32..=126 => table[(code - 32) as usize] as f32,
+            _ => 556.0,
+        }
+    }
+
+    fn width(self, size: f32, text: &str) -> f32 {
+        text.chars().map(|c| self.advance(c)).sum::<f32>() * size / 1000.0
+    }
+}
+
+#[rustfmt::skip]
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+#[rustfmt::skip]
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Writes the cheatsheet as a PDF according to `options`.
+pub fn export(keybindings: &[Keybinding], options: &PdfOptions) -> io::Result<()> {
+    fs::write(&options.output, render(keybindings, options))
+}
+
+/// Lays out the category cards and returns the encoded PDF document.
+pub fn render(keybindings: &[Keybinding], options: &PdfOptions) -> Vec<u8> {
+    let (mut width, mut height) = options.paper.size();
+    if options.landscape {
+        std::mem::swap(&mut width, &mut height);
+    }
+    let columns = options
+        .columns
+        .unwrap_or(if options.landscape { 3 } else { 2 });
+
+    let mut layout = Layout::new(width, height, columns);
+    for category in &Category::ALL {
+        let bindings: Vec<&Keybinding> = keybindings
+            .iter()
+            .filter(|kb| kb.category == *category)
+            .collect();
+        if !bindings.is_empty() {
+            layout.place_card(category, &bindings);
+        }
+    }
+
+    let pages = layout.finish();
+    encode(&pages, width, height)
+}
+
+/// Column-major flow of cards over as many pages as needed.
+struct Layout {
+    width: f32,
+    height: f32,
+    columns: usize,
+    column_width: f32,
+    pages: Vec<Canvas>,
+    column: usize,
+    cursor: f32,
+}
+
+impl Layout {
+    fn new(width: f32, height: f32, columns: usize) -> Self {
+        let column_width =
+            (width - 2.0 * MARGIN - (columns as f32 - 1.0) * GUTTER) / columns as f32;
+        let mut layout = Self {
+            width,
+            height,
+            columns,
+            column_width,
+            pages: Vec::new(),
+            column: 0,
+            cursor: 0.0,
+        };
+        layout.new_page();
+        layout
+    }
+
+    fn content_top(&self) -> f32 {
+        if self.pages.len() == 1 {
+            MARGIN + TITLE_HEIGHT
+        } else {
+            MARGIN
+        }
+    }
+
+    fn content_bottom(&self) -> f32 {
+        self.height - MARGIN - FOOTER_HEIGHT
+    }
+
+    fn column_x(&self) -> f32 {
+        MARGIN + self.column as f32 * (self.column_width + GUTTER)
+    }
+
+    fn new_page(&mut self) {
+        let mut canvas = Canvas::new(self.height);
+        if self.pages.is_empty() {
+            canvas.text(
+                MARGIN,
+                MARGIN + TITLE_SIZE,
+                Font::Bold,
+                TITLE_SIZE,
+                TEXT,
+                "DWM Keybinding Cheatsheet",
+            );
+        }
+        self.pages.push(canvas);
+        self.column = 0;
+        self.cursor = self.content_top();
+    }
+
+    fn next_column(&mut self) {
+        if self.column + 1 < self.columns {
+            self.column += 1;
+            self.cursor = self.content_top();
+        } else {
+            self.new_page();
+        }
+    }
+
+    fn at_column_top(&self) -> bool {
+        self.cursor <= self.content_top()
+    }
+
+    fn place_card(&mut self, category: &Category, keybindings: &[&Keybinding]) {
+        let inner_width = self.column_width - 2.0 * CARD_PADDING;
+        let rows: Vec<RowLayout> = keybindings
+            .iter()
+            .map(|kb| RowLayout::new(kb, inner_width))
+            .collect();
+
+        let mut remaining = &rows[..];
+        let mut continued = false;
+        while !remaining.is_empty() {
+            // Fit as many rows as possible into the current column, always at least one
+            // when the column is empty so oversized rows cannot loop forever.
+            let available = self.content_bottom() - self.cursor;
+            let mut used = HEADER_HEIGHT + 2.0 * CARD_PADDING;
+            let mut count = 0;
+            for row in remaining {
+                let extra = row.height + if count > 0 { ROW_GAP } else { 0.0 };
+                if used + extra > available && !(count == 0 && self.at_column_top()) {
+                    break;
+                }
+                used += extra;
+                count += 1;
+            }
+
+            if count == 0 {
+                self.next_column();
+                continue;
+            }
+
+            let (segment, rest) = remaining.split_at(count);
+            let title = if continued {
+                format!("{} (cont.)", category.as_str())
+            } else {
+                category.as_str().to_string()
+            };
+            self.draw_card(category, &title, segment, used);
+            remaining = rest;
+            continued = true;
+
+            self.cursor += used + CARD_GAP;
+            if !remaining.is_empty() {
+                self.next_column();
+            }
+        }
+    }
+
+    fn draw_card(&mut self, category: &Category, title: &str, rows: &[RowLayout], height: f32) {
+        let x = self.column_x();
+        let y = self.cursor;
+        let width = self.column_width;
+        let accent = rgb(category.rgb());
+        let canvas = self.pages.last_mut().expect("layout always has a page");
+
+        canvas.rounded_rect(x, y, width, height, [CARD_RADIUS; 4], Some(WHITE), Some(OUTLINE));
+        canvas.rounded_rect(
+            x,
+            y,
+            width,
+            HEADER_HEIGHT,
+            [CARD_RADIUS, CARD_RADIUS, 0.0, 0.0],
+            Some(mix(accent, WHITE, 0.7)),
+            None,
+        );
+        canvas.rounded_rect(x + CARD_PADDING, y + 5.0, 2.5, HEADER_HEIGHT - 10.0, [1.25; 4], Some(accent), None);
+        canvas.text(
+            x + CARD_PADDING + 8.0,
+            y + HEADER_HEIGHT / 2.0 + HEADER_SIZE * 0.35,
+            Font::Bold,
+            HEADER_SIZE,
+            TEXT,
+            title,
+        );
+
+        let mut row_y = y + HEADER_HEIGHT + CARD_PADDING;
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                let divider_y = row_y + ROW_GAP / 2.0;
+                canvas.line(x + CARD_PADDING, divider_y, x + width - CARD_PADDING, divider_y, mix(OUTLINE, WHITE, 0.5));
+                row_y += ROW_GAP;
+            }
+            row.draw(canvas, x + CARD_PADDING, row_y, width - 2.0 * CARD_PADDING, darken(accent, 0.55));
+            row_y += row.height;
+        }
+    }
+
+    fn finish(mut self) -> Vec<Canvas> {
+        let total = self.pages.len();
+        for (i, page) in self.pages.iter_mut().enumerate() {
+            let label = format!("Page {} of {}", i + 1, total);
+            let label_width = Font::Regular.width(DESCRIPTION_SIZE, &label);
+            page.text(
+                self.width - MARGIN - label_width,
+                self.height - MARGIN,
+                Font::Regular,
+                DESCRIPTION_SIZE,
+                TEXT_VARIANT,
+                &label,
+            );
+        }
+        self.pages
+    }
+}
+
+enum Piece {
+    Chip(String),
+    Plus,
+}
+
+impl Piece {
+    fn width(&self) -> f32 {
+        match self {
+            Piece::Chip(text) => Font::Mono.width(CHIP_SIZE, text) + 2.0 * CHIP_PADDING,
+            Piece::Plus => Font::Regular.width(CHIP_SIZE, "+"),
+        }
+    }
+}
+
+/// Pre-measured keybinding row: key chips, function name and wrapped description.
+struct RowLayout {
+    chip_lines: Vec<Vec<Piece>>,
+    function: String,
+    function_inline: bool,
+    description: Vec<String>,
+    height: f32,
+}
+
+impl RowLayout {
+    fn new(keybinding: &Keybinding, width: f32) -> Self {
+        let mut pieces = Vec::new();
+        for (i, modifier) in keybinding.modifiers.iter().enumerate() {
+            pieces.push(Piece::Chip(modifier.clone()));
+            if i < keybinding.modifiers.len() - 1 || !keybinding.key.is_empty() {
+                pieces.push(Piece::Plus);
+            }
+        }
+        if !keybinding.key.is_empty() {
+            pieces.push(Piece::Chip(keybinding.key.clone()));
+        }
+
+        let mut chip_lines: Vec<Vec<Piece>> = vec![Vec::new()];
+        let mut line_width = 0.0;
+        for piece in pieces {
+            let piece_width = piece.width() + CHIP_SPACING;
+            if line_width + piece_width > width && line_width > 0.0 {
+                chip_lines.push(Vec::new());
+                line_width = 0.0;
+            }
+            line_width += piece_width;
+            chip_lines.last_mut().expect("at least one line").push(piece);
+        }
+
+        let function_width = Font::Bold.width(FUNCTION_SIZE, &keybinding.function);
+        let function_inline = chip_lines.len() == 1 && line_width + 8.0 + function_width <= width;
+        let description = wrap(&keybinding.description, Font::Regular, DESCRIPTION_SIZE, width);
+
+        let chips_height = chip_lines.len() as f32 * (CHIP_HEIGHT + CHIP_SPACING) - CHIP_SPACING;
+        let mut height = chips_height;
+        if !function_inline {
+            height += 2.0 + FUNCTION_SIZE * LINE_HEIGHT;
+        }
+        if !description.is_empty() {
+            height += 2.0 + description.len() as f32 * DESCRIPTION_SIZE * LINE_HEIGHT;
+        }
+
+        Self {
+            chip_lines,
+            function: keybinding.function.clone(),
+            function_inline,
+            description,
+            height,
+        }
+    }
+
+    fn draw(&self, canvas: &mut Canvas, x: f32, y: f32, width: f32, function_color: [f32; 3]) {
+        let mut line_y = y;
+        for line in &self.chip_lines {
+            let mut chip_x = x;
+            for piece in line {
+                let piece_width = piece.width();
+                match piece {
+                    Piece::Chip(text) => draw_keycap(canvas, chip_x, line_y, piece_width, text),
+                    Piece::Plus => canvas.text(
+                        chip_x,
+                        line_y + CHIP_HEIGHT / 2.0 + CHIP_SIZE * 0.35,
+                        Font::Regular,
+                        CHIP_SIZE,
+                        TEXT_VARIANT,
+                        "+",
+                    ),
+                }
+                chip_x += piece_width + CHIP_SPACING;
+            }
+            line_y += CHIP_HEIGHT + CHIP_SPACING;
+        }
+        line_y -= CHIP_SPACING;
+
+        if self.function_inline {
+            let function_width = Font::Bold.width(FUNCTION_SIZE, &self.function);
+            canvas.text(
+                x + width - function_width,
+                y + CHIP_HEIGHT / 2.0 + FUNCTION_SIZE * 0.35,
+                Font::Bold,
+                FUNCTION_SIZE,
+                function_color,
+                &self.function,
+            );
+        } else {
+            line_y += 2.0 + FUNCTION_SIZE * LINE_HEIGHT;
+            canvas.text(x, line_y - FUNCTION_SIZE * 0.25, Font::Bold, FUNCTION_SIZE, function_color, &self.function);
+        }
+
+        if !self.description.is_empty() {
+            line_y += 2.0;
+            for line in &self.description {
+                line_y += DESCRIPTION_SIZE * LINE_HEIGHT;
+                canvas.text(x, line_y - DESCRIPTION_SIZE * 0.25, Font::Regular, DESCRIPTION_SIZE, TEXT_VARIANT, line);
+            }
+        }
+    }
+}
+
+/// Draws a key chip as a keycap: a darker base peeking out below a rounded top.
+fn draw_keycap(canvas: &mut Canvas, x: f32, y: f32, width: f32, text: &str) {
+    canvas.rounded_rect(x, y, width, CHIP_HEIGHT, [2.5; 4], Some(CHIP_SHADOW), None);
+    canvas.rounded_rect(x, y, width, CHIP_HEIGHT - 1.5, [2.5; 4], Some(CHIP_FILL), Some(CHIP_SHADOW));
+    canvas.text(
+        x + CHIP_PADDING,
+        y + (CHIP_HEIGHT - 1.5) / 2.0 + CHIP_SIZE * 0.3,
+        Font::Mono,
+        CHIP_SIZE,
+        TEXT,
+        text,
+    );
+}
+
+/// Greedy word wrap using the font metrics.
+fn wrap(text: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if font.width(size, &candidate) > width && !current.is_empty() {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        } else {
+            current = candidate;
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn rgb([r, g, b]: [u8; 3]) -> [f32; 3] {
+    [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0]
+}
+
+fn mix(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+    ]
+}
+
+fn darken(color: [f32; 3], factor: f32) -> [f32; 3] {
+    [color[0] * factor, color[1] * factor, color[2] * factor]
+}
+
+/// Content stream of a single page, addressed top-down like the GUI.
+struct Canvas {
+    height: f32,
+    ops: String,
+}
+
+impl Canvas {
+    fn new(height: f32) -> Self {
+        Self {
+            height,
+            ops: String::new(),
+        }
+    }
+
+    fn y(&self, y: f32) -> f32 {
+        self.height - y
+    }
+
+    /// Rectangle with per-corner radii in the order top-left, top-right,
+    /// bottom-right, bottom-left.
+    #[allow(clippy::too_many_arguments)]
+    fn rounded_rect(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        radii: [f32; 4],
+        fill: Option<[f32; 3]>,
+        stroke: Option<[f32; 3]>,
+    ) {
+        // Control point distance approximating a quarter circle with a cubic Bézier
+        const K: f32 = 0.552_284_8;
+        let [tl, tr, br, bl] = radii;
+        let (left, right) = (x, x + width);
+        let (top, bottom) = (self.y(y), self.y(y + height));
+
+        let ops = &mut self.ops;
+        let _ = writeln!(ops, "{:.2} {:.2} m", left + tl, top);
+        let _ = writeln!(ops, "{:.2} {:.2} l", right - tr, top);
+        if tr > 0.0 {
+            let _ = writeln!(
+                ops,
+                "{:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c",
+                right - tr + tr * K, top, right, top - tr + tr * K, right, top - tr
+            );
+        }
+        let _ = writeln!(ops, "{:.2} {:.2} l", right, bottom + br);
+        if br > 0.0 {
+            let _ = writeln!(
+                ops,
+                "{:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c",
+                right, bottom + br - br * K, right - br + br * K, bottom, right - br, bottom
+            );
+        }
+        let _ = writeln!(ops, "{:.2} {:.2} l", left + bl, bottom);
+        if bl > 0.0 {
+            let _ = writeln!(
+                ops,
+                "{:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c",
+                left + bl - bl * K, bottom, left, bottom + bl - bl * K, left, bottom + bl
+            );
+        }
+        let _ = writeln!(ops, "{:.2} {:.2} l", left, top - tl);
+        if tl > 0.0 {
+            let _ = writeln!(
+                ops,
+                "{:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c",
+                left, top - tl + tl * K, left + tl - tl * K, top, left + tl, top
+            );
+        }
+        let _ = writeln!(ops, "h");
+
+        if let Some([r, g, b]) = fill {
+            let _ = writeln!(ops, "{:.3} {:.3} {:.3} rg", r, g, b);
+        }
+        if let Some([r, g, b]) = stroke {
+            let _ = writeln!(ops, "{:.3} {:.3} {:.3} RG 0.5 w", r, g, b);
+        }
+        let paint = match (fill.is_some(), stroke.is_some()) {
+            (true, true) => "B",
+            (true, false) => "f",
+            (false, true) => "S",
+            (false, false) => "n",
+        };
+        let _ = writeln!(ops, "{}", paint);
+    }
+
+    fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, [r, g, b]: [f32; 3]) {
+        let (y1, y2) = (self.y(y1), self.y(y2));
+        let _ = writeln!(
+            self.ops,
+            "{:.3} {:.3} {:.3} RG 0.5 w {:.2} {:.2} m {:.2} {:.2} l S",
+            r, g, b, x1, y1, x2, y2
+        );
+    }
+
+    /// Draws `text` with its baseline at `baseline`.
+    fn text(&mut self, x: f32, baseline: f32, font: Font, size: f32, [r, g, b]: [f32; 3], text: &str) {
+        let baseline = self.y(baseline);
+        let _ = writeln!(
+            self.ops,
+            "BT {:.3} {:.3} {:.3} rg /{} {:.1} Tf {:.2} {:.2} Td ({}) Tj ET",
+            r,
+            g,
+            b,
+            font.resource(),
+            size,
+            x,
+            baseline,
+            escape(text)
+        );
+    }
+}
+
+/// Escapes a string for a PDF literal in WinAnsiEncoding.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            '\u{a0}'..='\u{ff}' => {
+                let _ = write!(escaped, "\\{:03o}", c as u32);
+            }
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+fn encode(pages: &[Canvas], width: f32, height: f32) -> Vec<u8> {
+    const FONTS: [Font; 3] = [Font::Regular, Font::Bold, Font::Mono];
+    // Object ids: 1 catalog, 2 page tree, 3 info, 4..=6 fonts, then page/content pairs
+    let first_page = 4 + FONTS.len();
+    let page_id = |i: usize| first_page + 2 * i;
+
+    let mut objects: Vec<Vec<u8>> = Vec::new();
+    objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+
+    let kids: Vec<String> = (0..pages.len()).map(|i| format!("{} 0 R", page_id(i))).collect();
+    objects.push(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} /MediaBox [0 0 {:.2} {:.2}] >>",
+            kids.join(" "),
+            pages.len(),
+            width,
+            height
+        )
+        .into_bytes(),
+    );
+    objects.push(b"<< /Title (DWM Keybinding Cheatsheet) /Producer (dwm-cheatsheet) >>".to_vec());
+
+    for font in FONTS {
+        objects.push(
+            format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                font.base_font()
+            )
+            .into_bytes(),
+        );
+    }
+
+    let font_resources: Vec<String> = FONTS
+        .iter()
+        .enumerate()
+        .map(|(i, font)| format!("/{} {} 0 R", font.resource(), 4 + i))
+        .collect();
+    for (i, page) in pages.iter().enumerate() {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /Resources << /Font << {} >> >> /Contents {} 0 R >>",
+                font_resources.join(" "),
+                page_id(i) + 1
+            )
+            .into_bytes(),
+        );
+        let mut stream = format!("<< /Length {} >>\nstream\n", page.ops.len()).into_bytes();
+        stream.extend_from_slice(page.ops.as_bytes());
+        stream.extend_from_slice(b"\nendstream");
+        objects.push(stream);
+    }
+
+    let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        out.extend_from_slice(object);
+        out.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref = out.len();
+    out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R /Info 3 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .as_bytes(),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keybinding(key: &str, description: &str) -> Keybinding {
+        Keybinding {
+            modifiers: vec!["Super".to_string()],
+            key: key.to_string(),
+            function: "spawn".to_string(),
+            description: description.to_string(),
+            category: Category::Applications,
+            action: None,
+        }
+    }
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).position(|window| window == needle)
+    }
+
+    #[test]
+    fn xref_offsets_point_at_their_objects() {
+        let pdf = render(&[keybinding("Return", "Open a terminal")], &PdfOptions::default());
+        let xref = find(&pdf, b"xref\n").expect("xref table");
+        let startxref = find(&pdf, b"startxref\n").expect("startxref");
+        let tail = String::from_utf8(pdf[startxref..].to_vec()).unwrap();
+        assert_eq!(tail.lines().nth(1), Some(xref.to_string().as_str()));
+
+        let table = String::from_utf8(pdf[xref..startxref].to_vec()).unwrap();
+        let entries: Vec<&str> = table.lines().skip(3).take_while(|line| line.ends_with(" n ")).collect();
+        assert_eq!(table.lines().nth(1), Some(format!("0 {}", entries.len() + 1).as_str()));
+        for (i, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            let header = format!("{} 0 obj\n", i + 1);
+            assert!(pdf[offset..].starts_with(header.as_bytes()), "object {} isn't at {}", i + 1, offset);
+        }
+    }
+
+    #[test]
+    fn cards_flow_onto_a_second_page_when_the_first_is_full() {
+        let one = String::from_utf8_lossy(&render(&[keybinding("a", "One")], &PdfOptions::default())).into_owned();
+        assert!(one.contains("/Count 1 "));
+
+        let keybindings: Vec<Keybinding> = (0..200)
+            .map(|i| keybinding(&format!("F{}", i), "Starts one of many applications"))
+            .collect();
+        let many = String::from_utf8_lossy(&render(&keybindings, &PdfOptions::default())).into_owned();
+        assert!(!many.contains("/Count 1 "));
+        assert!(many.contains("Page 2 of "));
+        assert!(many.contains("(Applications \\(cont.\\)) Tj"));
+        // Every row is drawn exactly once across the pages
+        assert!(many.contains("(F0) Tj") && many.contains("(F199) Tj"));
+        assert_eq!(many.matches("(F19) Tj").count(), 1);
+    }
+
+    #[test]
+    fn descriptions_escape_parentheses_and_backslashes() {
+        assert_eq!(escape(r"a (b) \c"), r"a \(b\) \\c");
+        assert_eq!(escape("caf\u{e9} \u{2192}"), r"caf\351 ?");
+
+        let pdf = render(&[keybinding("p", r"Print (all) to C:\out")], &PdfOptions::default());
+        let pdf = String::from_utf8_lossy(&pdf);
+        assert!(pdf.contains(r"(Print \(all\) to C:\\out) Tj"), "{}", pdf);
+    }
+}
@@ -0,0 +1,271 @@
+use crate::config::Action;
+use std::env;
+use std::fs::{self, File};
+use std::io::Read;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Lines of a file shown in the detail pane; the rest is left to the editor.
+const MAX_LINES: usize = 40;
+
+/// Bytes read from the start of a file, enough for [`MAX_LINES`] lines.
+const MAX_BYTES: u64 = 64 * 1024;
+
+/// A local file a command refers to, e.g. the script in
+/// `sh ~/.config/chadwm/scripts/kblayout.sh` or rofi's `-config` theme.
+pub struct FilePreview {
+    /// As written in the command.
+    pub written: String,
+    /// With `~` and `$HOME` expanded.
+    pub path: PathBuf,
+    pub contents: Contents,
+    pub language: Language,
+}
+
+pub enum Contents {
+    Missing,
+    Directory,
+    /// A device, FIFO or socket, which isn't read.
+    Special,
+    Binary { bytes: u64 },
+    /// The first [`MAX_LINES`] lines, and whether there were more.
+    Text { text: String, truncated: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Shell,
+    Rasi,
+    Plain,
+}
+
+/// What a highlighted stretch of text is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    Comment,
+    String,
+    Keyword,
+    /// `$VAR` in shell, a `property:` in rasi.
+    Variable,
+    Number,
+    Plain,
+}
+
+/// Every file path among the arguments of the command `action` spawns, in
+/// order and without repeats, read for previewing. Programs, such as the shell
+/// or `/usr/bin/pactl` in a `SHCMD`, and `/dev` paths are left out.
+pub fn referenced_files(action: &Action) -> Vec<FilePreview> {
+    let Some(argv) = action.command() else {
+        return Vec::new();
+    };
+    let separator = |c: char| "|;&()`\n".contains(c);
+    let arguments: Vec<&str> = match argv {
+        // Each command of the line without its program word
+        [sh, flag, line] if sh == "/bin/sh" && flag == "-c" => line
+            .split(separator)
+            .flat_map(|command| command.split_whitespace().skip(1))
+            .collect(),
+        [_, args @ ..] => args
+            .iter()
+            .flat_map(|arg| arg.split(|c: char| c.is_whitespace() || separator(c)))
+            .collect(),
+        [] => Vec::new(),
+    };
+    let mut written: Vec<String> = Vec::new();
+    let words = arguments
+        .into_iter()
+        .map(|word| word.trim_matches(|c| c == '"' || c == '\''))
+        // `--config=~/file`
+        .map(|word| word.rsplit_once('=').map_or(word, |(_, value)| value));
+    for word in words {
+        let looks_like_path = word.starts_with("~/") || word.starts_with("$HOME/") || word.starts_with('/');
+        let device = word.starts_with("/dev/");
+        if looks_like_path && !device && word.len() > 1 && !written.iter().any(|w| w == word) {
+            written.push(word.to_string());
+        }
+    }
+    written.into_iter().map(read).collect()
+}
+
+fn read(written: String) -> FilePreview {
+    let path = expand_home(&written);
+    let contents = match fs::metadata(&path) {
+        Err(_) => Contents::Missing,
+        Ok(meta) if meta.is_dir() => Contents::Directory,
+        // Opening a FIFO would block until something writes to it
+        Ok(meta) if !meta.is_file() => Contents::Special,
+        Ok(meta) => match prefix(&path) {
+            Some(text) => {
+                let truncated = meta.len() > MAX_BYTES || text.lines().count() > MAX_LINES;
+                let text = text.lines().take(MAX_LINES).collect::<Vec<_>>().join("\n");
+                Contents::Text { text, truncated }
+            }
+            None => Contents::Binary { bytes: meta.len() },
+        },
+    };
+    let language = language(&path, &contents);
+    FilePreview {
+        written,
+        path,
+        contents,
+        language,
+    }
+}
+
+/// The first [`MAX_BYTES`] of the file as text, `None` if it isn't UTF-8 or
+/// can't be read. A character cut off at the end of the prefix is dropped.
+fn prefix(path: &Path) -> Option<String> {
+    let mut bytes = Vec::new();
+    File::open(path).ok()?.take(MAX_BYTES).read_to_end(&mut bytes).ok()?;
+    match String::from_utf8(bytes) {
+        Ok(text) => Some(text),
+        Err(err) if err.utf8_error().error_len().is_none() => {
+            let valid = err.utf8_error().valid_up_to();
+            let mut bytes = err.into_bytes();
+            bytes.truncate(valid);
+            String::from_utf8(bytes).ok()
+        }
+        Err(_) => None,
+    }
+}
+
+fn expand_home(written: &str) -> PathBuf {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let rest = written.strip_prefix("~/").or_else(|| written.strip_prefix("$HOME/"));
+    match (rest, home) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(written),
+    }
+}
+
+/// By extension, or a shell shebang for extensionless scripts.
+fn language(path: &Path, contents: &Contents) -> Language {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("sh" | "bash" | "zsh") => Language::Shell,
+        Some("rasi") => Language::Rasi,
+        _ => match contents {
+            Contents::Text { text, .. } if text.starts_with("#!") && text.lines().next().is_some_and(|l| l.contains("sh")) => {
+                Language::Shell
+            }
+            _ => Language::Plain,
+        },
+    }
+}
+
+const SHELL_KEYWORDS: [&str; 17] = [
+    "if", "then", "else", "elif", "fi", "for", "in", "do", "done", "while", "until", "case", "esac", "function",
+    "local", "export", "return",
+];
+
+/// Splits `text` into highlighted stretches covering all of it: comments,
+/// quoted strings, keywords, variables and numbers for shell; comments,
+/// strings, properties and numbers for rasi.
+pub fn highlight(text: &str, language: Language) -> Vec<(Range<usize>, Token)> {
+    if language == Language::Plain {
+        return vec![(0..text.len(), Token::Plain)];
+    }
+    let bytes = text.as_bytes();
+    let mut spans = Vec::new();
+    let mut plain_start = 0;
+    let mut i = 0;
+    let push = |spans: &mut Vec<(Range<usize>, Token)>, plain_start: &mut usize, range: Range<usize>, token| {
+        if *plain_start < range.start {
+            spans.push((*plain_start..range.start, Token::Plain));
+        }
+        *plain_start = range.end;
+        spans.push((range, token));
+    };
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        let word_start = i == 0 || !(bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_' || bytes[i - 1] == b'-');
+        // Comments run to the end of the line (`/* */` blocks in rasi to their end)
+        let line_comment = match language {
+            Language::Shell => c == b'#' && (i == 0 || bytes[i - 1].is_ascii_whitespace()),
+            _ => text[i..].starts_with("//"),
+        };
+        if line_comment {
+            i = text[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            push(&mut spans, &mut plain_start, start..i, Token::Comment);
+        } else if language == Language::Rasi && text[i..].starts_with("/*") {
+            i = text[i + 2..].find("*/").map_or(bytes.len(), |n| i + 2 + n + 2);
+            push(&mut spans, &mut plain_start, start..i, Token::Comment);
+        } else if c == b'"' || c == b'\'' {
+            i += 1;
+            while i < bytes.len() && bytes[i] != c {
+                i += if bytes[i] == b'\\' && c == b'"' { 2 } else { 1 };
+            }
+            i = (i + 1).min(bytes.len());
+            push(&mut spans, &mut plain_start, start..i, Token::String);
+        } else if language == Language::Shell && c == b'$' {
+            i += 1;
+            if i < bytes.len() && bytes[i] == b'{' {
+                i = text[i..].find('}').map_or(bytes.len(), |n| i + n + 1);
+            } else {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+            }
+            push(&mut spans, &mut plain_start, start..i, Token::Variable);
+        } else if word_start && c.is_ascii_digit() {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.' || bytes[i] == b'%') {
+                i += 1;
+            }
+            push(&mut spans, &mut plain_start, start..i, Token::Number);
+        } else if word_start && (c.is_ascii_alphabetic() || c == b'_' || c == b'-' || (c == b'@' && language == Language::Rasi)) {
+            i += 1;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'-') {
+                i += 1;
+            }
+            let word = &text[start..i];
+            let token = match language {
+                Language::Shell if SHELL_KEYWORDS.contains(&word) => Some(Token::Keyword),
+                Language::Rasi if text[i..].trim_start_matches([' ', '\t']).starts_with(':') => Some(Token::Variable),
+                Language::Rasi if word == "configuration" || word.starts_with('@') => Some(Token::Keyword),
+                _ => None,
+            };
+            if let Some(token) = token {
+                push(&mut spans, &mut plain_start, start..i, token);
+            }
+        } else {
+            // Step over the whole character, which may be several bytes
+            i += text[i..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    if plain_start < bytes.len() {
+        spans.push((plain_start..bytes.len(), Token::Plain));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Argument;
+
+    fn written(argv: &[&str]) -> Vec<String> {
+        let action = Action {
+            function: "spawn".to_string(),
+            argument: Argument::Command(argv.iter().map(|arg| arg.to_string()).collect()),
+            line: 1,
+            end_line: 1,
+            source: String::new(),
+            file: None,
+            disabled: false,
+        };
+        referenced_files(&action).into_iter().map(|file| file.written).collect()
+    }
+
+    #[test]
+    fn programs_are_not_previewed() {
+        assert!(written(&["/usr/bin/pactl", "set-sink-volume", "@DEFAULT_SINK@", "+5%"]).is_empty());
+        assert!(written(&["/bin/sh", "-c", "/usr/bin/pactl set-sink-mute @DEFAULT_SINK@ toggle"]).is_empty());
+        assert_eq!(written(&["/bin/sh", "-c", "maim | /usr/bin/xclip -i /tmp/shot.png"]), ["/tmp/shot.png"]);
+    }
+
+    #[test]
+    fn arguments_are_previewed_except_devices() {
+        assert_eq!(written(&["/bin/sh", "-c", "sh ~/scripts/kb.sh > /dev/null"]), ["~/scripts/kb.sh"]);
+        assert_eq!(written(&["rofi", "-config", "/etc/rofi.rasi", "-theme=/etc/rofi.rasi"]), ["/etc/rofi.rasi"]);
+    }
+}
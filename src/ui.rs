@@ -1,23 +1,41 @@
-use crate::keybindings::{get_keybindings, Category, Keybinding};
+use crate::actions;
+use crate::compare::{self, Diff};
+use crate::config::{DwmConfig, Layout};
+use crate::daemon;
+use crate::deps;
+use crate::groups::{self, Group, GroupBy};
+use crate::ipc::{self, DwmState};
+use crate::monitor::{self, WindowGeometry};
+use crate::pairs;
+use crate::preview::{self, Contents, FilePreview};
+use crate::window;
+use crate::keybindings::{self, Category, Keybinding};
+use crate::xtest;
 use eframe::egui;
 use egui::{
     Color32, Frame, Margin, RichText, Rounding, ScrollArea, Stroke, Ui,
     Vec2,
 };
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
 
 // Material 3 Dark Theme Colors
-struct MaterialColors;
+pub(crate) struct MaterialColors;
 
 impl MaterialColors {
-    const SURFACE: Color32 = Color32::from_rgb(20, 18, 24);
-    const SURFACE_CONTAINER: Color32 = Color32::from_rgb(33, 31, 38);
-    const SURFACE_CONTAINER_HIGH: Color32 = Color32::from_rgb(43, 41, 48);
-    const ON_SURFACE: Color32 = Color32::from_rgb(230, 224, 233);
-    const ON_SURFACE_VARIANT: Color32 = Color32::from_rgb(202, 196, 208);
-    const OUTLINE: Color32 = Color32::from_rgb(147, 143, 153);
-    const OUTLINE_VARIANT: Color32 = Color32::from_rgb(73, 69, 79);
-    const PRIMARY: Color32 = Color32::from_rgb(208, 188, 255);
+    pub(crate) const SURFACE: Color32 = Color32::from_rgb(20, 18, 24);
+    pub(crate) const SURFACE_CONTAINER: Color32 = Color32::from_rgb(33, 31, 38);
+    pub(crate) const SURFACE_CONTAINER_HIGH: Color32 = Color32::from_rgb(43, 41, 48);
+    pub(crate) const ON_SURFACE: Color32 = Color32::from_rgb(230, 224, 233);
+    pub(crate) const ON_SURFACE_VARIANT: Color32 = Color32::from_rgb(202, 196, 208);
+    pub(crate) const OUTLINE: Color32 = Color32::from_rgb(147, 143, 153);
+    pub(crate) const OUTLINE_VARIANT: Color32 = Color32::from_rgb(73, 69, 79);
+    pub(crate) const PRIMARY: Color32 = Color32::from_rgb(208, 188, 255);
+    pub(crate) const ERROR: Color32 = Color32::from_rgb(242, 184, 181);
     // const ON_PRIMARY: Color32 = Color32::from_rgb(56, 30, 114);
     // const PRIMARY_CONTAINER: Color32 = Color32::from_rgb(79, 55, 139);
     // const ON_PRIMARY_CONTAINER: Color32 = Color32::from_rgb(234, 221, 255);
@@ -27,14 +45,301 @@ impl MaterialColors {
     // const ON_TERTIARY_CONTAINER: Color32 = Color32::from_rgb(255, 216, 228);
 }
 
+/// What a click on a row's action button asks for.
+#[derive(Clone, Copy)]
+enum RowAction {
+    /// Spawn the binding's command.
+    Run,
+    /// Call the binding's function through dwm's IPC socket.
+    Dwm,
+    /// Close the cheatsheet and replay the combo so dwm performs it.
+    Trigger,
+}
+
+/// How long the replayed combo waits for the cheatsheet window to go away and
+/// focus to return to the previous client.
+const TRIGGER_DELAY_MS: &str = "250";
+
+/// Window behaviour chosen on the command line.
+#[derive(Debug, Clone, Default)]
+pub struct WindowOptions {
+    /// Borderless, always-on-top popup over the focused monitor that goes away on
+    /// focus loss, an unbound key or `timeout`.
+    pub overlay: bool,
+    /// Idle time after which the overlay dismisses itself.
+    pub timeout: Option<Duration>,
+    /// `WM_CLASS` class and instance for dwm rules, `dwm-cheatsheet` by default.
+    pub class: Option<String>,
+    pub name: Option<String>,
+    /// Size and position, relative to `monitor` when one is given.
+    pub geometry: Option<WindowGeometry>,
+    /// Monitor to open on, in dwm's numbering.
+    pub monitor: Option<usize>,
+    /// Forget the saved window placement and UI state before starting.
+    pub reset_state: bool,
+    /// What the window opens on.
+    pub view: View,
+    /// Whether the search bar takes keyboard focus, true by default.
+    pub focus_search: Option<bool>,
+}
+
+/// The bindings a window opens on, narrowed from the command line so a dwm
+/// binding can jump straight to e.g. the gaps card.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct View {
+    /// Search text, replacing whatever was typed last.
+    pub search: Option<String>,
+    /// Categories to show, all when empty.
+    pub categories: Vec<Category>,
+    /// Modifier every shown binding has to use.
+    pub modifier: Option<String>,
+}
+
+impl View {
+    fn shows(&self, keybinding: &Keybinding) -> bool {
+        (self.categories.is_empty() || self.categories.contains(&keybinding.category))
+            && self
+                .modifier
+                .as_ref()
+                .is_none_or(|modifier| keybinding.modifiers.contains(modifier))
+    }
+}
+
+/// Window size when neither `--geometry` nor the overlay set one.
+pub const DEFAULT_SIZE: (u32, u32) = (1200, 800);
+
+impl WindowOptions {
+    /// The options that only take effect when a window starts, as flags, for a
+    /// running daemon to check a forwarded `toggle` or `show` against.
+    pub fn startup_flags(&self) -> Vec<String> {
+        let mut flags = Vec::new();
+        if self.overlay {
+            flags.push("--overlay".to_string());
+        }
+        if let Some(timeout) = self.timeout {
+            flags.push(format!("--timeout {}", timeout.as_secs_f32()));
+        }
+        if let Some(class) = &self.class {
+            flags.push(format!("--class {}", class));
+        }
+        if let Some(name) = &self.name {
+            flags.push(format!("--name {}", name));
+        }
+        if let Some(geometry) = self.geometry {
+            flags.push(format!("--geometry {}", geometry));
+        }
+        if let Some(monitor) = self.monitor {
+            flags.push(format!("--monitor {}", monitor));
+        }
+        if self.reset_state {
+            flags.push("--reset-state".to_string());
+        }
+        if self.focus_search == Some(false) {
+            flags.push("--focus-search=false".to_string());
+        }
+        flags
+    }
+
+    /// Position and size from `--geometry`, `--monitor` and the overlay, which
+    /// covers its monitor unless a geometry is given. `None` leaves placement to
+    /// the window manager.
+    pub fn placement(&self, ipc_socket: &Path) -> Option<((i32, i32), (u32, u32))> {
+        let area = if self.overlay || self.monitor.is_some() {
+            monitor::target(ipc_socket, self.monitor)?
+        } else if self.geometry.is_some() {
+            monitor::screen()?
+        } else {
+            return None;
+        };
+        match self.geometry {
+            None if self.overlay => Some(((area.x, area.y), (area.width, area.height))),
+            geometry => Some(geometry.unwrap_or_default().place(area, DEFAULT_SIZE)),
+        }
+    }
+
+    /// Whether the window opens where it was last closed. An overlay,
+    /// `--geometry` or `--monitor` place it every time instead.
+    pub fn remembers_placement(&self) -> bool {
+        !self.overlay && self.geometry.is_none() && self.monitor.is_none()
+    }
+
+    pub fn class(&self) -> &str {
+        self.class.as_deref().unwrap_or(window::DEFAULT_CLASS)
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(window::DEFAULT_CLASS)
+    }
+
+    pub fn focus_search(&self) -> bool {
+        self.focus_search.unwrap_or(true)
+    }
+}
+
+/// How the window starts.
+pub struct Startup {
+    /// Socket to serve `toggle`/`show` requests on; the window then hides instead
+    /// of quitting.
+    pub daemon: Option<daemon::Listener>,
+    pub visible: bool,
+    pub options: WindowOptions,
+    /// Comparison to open at startup, from `diff --gui`.
+    pub diff: Option<Diff>,
+}
+
+/// UI state kept in eframe's storage between launches. eframe itself saves the
+/// window size and position next to it.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct SavedState {
+    search: String,
+    close_after_run: bool,
+    zoom: f32,
+    /// Cards folded to their header.
+    collapsed: Vec<Group>,
+    sidebar: bool,
+    /// Card order as arranged in the sidebar.
+    order: Vec<Category>,
+    view_mode: ViewMode,
+    /// Table column sorted by and whether ascending; card order when unset.
+    sort: Option<(SortColumn, bool)>,
+    group_by: GroupBy,
+    /// Whether opposite bindings share a row.
+    pair_rows: bool,
+    show_disabled: bool,
+}
+
+impl Default for SavedState {
+    fn default() -> Self {
+        SavedState {
+            search: String::new(),
+            close_after_run: false,
+            zoom: 1.0,
+            collapsed: Vec::new(),
+            sidebar: true,
+            order: Category::ALL.to_vec(),
+            view_mode: ViewMode::Cards,
+            sort: None,
+            group_by: GroupBy::Category,
+            pair_rows: true,
+            show_disabled: false,
+        }
+    }
+}
+
+/// Deletes the state eframe stores for windows of `class`, so the next start
+/// uses the defaults.
+pub fn reset_saved_state(class: &str) -> std::io::Result<()> {
+    let Some(dir) = eframe::storage_dir(class) else {
+        return Ok(());
+    };
+    match std::fs::remove_file(dir.join("app.ron")) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Backdrop of the overlay, translucent so the desktop shows through when a
+/// compositor is running.
+const OVERLAY_FILL: Color32 = Color32::from_rgba_premultiplied(18, 16, 22, 230);
+
+/// Id of the search field, to tell whether it has keyboard focus.
+const SEARCH_ID: &str = "search";
+
+/// Opacity of bindings that are commented out or in an `#if 0` block.
+const DISABLED_OPACITY: f32 = 0.45;
+
+/// The zoom factors egui's Ctrl+Plus and Ctrl+Minus shortcuts stay within.
+const ZOOM_RANGE: (f32, f32) = (0.2, 5.0);
+
 pub struct CheatsheetApp {
     keybindings: HashMap<Category, Vec<Keybinding>>,
     search_text: String,
+    /// Categories and modifier from the command line; the search is kept in
+    /// `search_text` once the window is up.
+    view: View,
+    /// Zoom factor as of the last frame, for saving.
+    zoom: f32,
     filtered_keybindings: HashMap<Category, Vec<Keybinding>>,
+    /// The filtered bindings as cards, grouped by `group_by`, in display order.
+    groups: Vec<(Group, Vec<Keybinding>)>,
+    group_by: GroupBy,
+    /// Show inc/dec bindings as one row, see [`pairs::rows`].
+    pair_rows: bool,
+    /// Show commented-out and `#if 0` bindings, greyed out.
+    show_disabled: bool,
+    close_after_run: bool,
+    /// Row picked with the keyboard, by card and index into its bindings.
+    selected: Option<(Group, usize)>,
+    /// Set when the selection moved, so the grid scrolls it into view.
+    scroll_to_selected: bool,
+    /// Cards folded to their header.
+    collapsed: HashSet<Group>,
+    /// Whether the category sidebar is expanded.
+    sidebar: bool,
+    /// Every category once, in the order cards are placed and walked.
+    order: Vec<Category>,
+    view_mode: ViewMode,
+    sort: Option<(SortColumn, bool)>,
+    /// Card heights as last drawn, to pack the columns.
+    card_heights: HashMap<Group, CardHeight>,
+    /// Card the sidebar asked to jump to this frame.
+    scroll_to_group: Option<Group>,
+    /// Binding whose detail pane is open.
+    details: Option<Keybinding>,
+    /// Whether the cheatsheet's own shortcut help is open.
+    show_shortcuts: bool,
+    /// Files the open binding's command refers to, read once per command.
+    previews: Option<(Vec<String>, Vec<FilePreview>)>,
+    /// Programs spawned by bindings that aren't installed, with the combos using them.
+    missing: BTreeMap<String, Vec<String>>,
+    /// Whether the missing dependencies list is open.
+    show_missing: bool,
+    /// The config against another one: the `config.def.h` beside it, or what
+    /// `diff --gui` was given.
+    comparison: Option<Diff>,
+    /// Whether the comparison with the other config file is open.
+    show_comparison: bool,
+    /// Destructive binding waiting for confirmation before it runs or is
+    /// triggered.
+    pending_run: Option<(Keybinding, RowAction)>,
+    /// Whether the search field takes keyboard focus next frame: on startup, when
+    /// shown again and after Escape, so other widgets can keep it otherwise.
+    focus_search: bool,
+    /// Outcome of the last run, shown under the header.
+    run_status: Option<Result<String, String>>,
+    layouts: Vec<Layout>,
+    ipc_socket: PathBuf,
+    /// Reply to the last IPC call, shown under the row of the binding (by combo).
+    dwm_reply: Option<(String, Result<String, String>)>,
+    /// Live state from dwm's IPC socket, `None` while dwm can't be reached.
+    dwm_state: Option<DwmState>,
+    dwm_updates: Receiver<Option<DwmState>>,
+    /// Replies to IPC calls made in the background, by combo.
+    dwm_replies: Receiver<(String, Result<String, String>)>,
+    reply_sender: mpsc::Sender<(String, Result<String, String>)>,
+    /// Requests from later invocations when running as the daemon.
+    daemon_requests: Option<Receiver<daemon::Request>>,
+    visible: bool,
+    options: WindowOptions,
+    /// Last input in the overlay, for its timeout.
+    last_activity: Instant,
+    /// Whether the overlay has had focus since it was shown; focus loss only
+    /// counts after that.
+    had_focus: bool,
 }
 
 impl CheatsheetApp {
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(
+        cc: &eframe::CreationContext<'_>,
+        keybindings_vec: Vec<Keybinding>,
+        config: DwmConfig,
+        startup: Startup,
+    ) -> Self {
+        if let Err(err) = window::set_wm_class(cc, startup.options.name(), startup.options.class()) {
+            eprintln!("dwm-cheatsheet: could not set WM_CLASS: {}", err);
+        }
+
         // Customize fonts
         let fonts = egui::FontDefinitions::default();
         // You could load custom fonts here (e.g. Roboto)
@@ -51,10 +356,12 @@ impl CheatsheetApp {
         style.spacing.window_margin = Margin::same(0.0);
         cc.egui_ctx.set_style(style);
 
-        let keybindings_vec = get_keybindings();
+        let missing = deps::missing(&keybindings_vec);
+        let show_comparison = startup.diff.is_some();
+        let comparison = startup.diff.or_else(|| compare::against_counterpart(&config));
         let mut keybindings: HashMap<Category, Vec<Keybinding>> = HashMap::new();
 
-        for keybinding in keybindings_vec {
+        for keybinding in keybindings_vec.into_iter().chain(keybindings::disabled_keybindings(&config)) {
             keybindings
                 .entry(keybinding.category.clone())
                 .or_default()
@@ -63,64 +370,921 @@ impl CheatsheetApp {
 
         let filtered_keybindings = keybindings.clone();
 
-        Self {
+        let saved: SavedState = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
+            .unwrap_or_default();
+        let zoom = restored_zoom(saved.zoom);
+        cc.egui_ctx.set_zoom_factor(zoom);
+
+        let daemon_requests = startup.daemon.map(|listener| {
+            let (sender, requests) = mpsc::channel();
+            let ctx = cc.egui_ctx.clone();
+            listener.serve(move |request| {
+                let _ = sender.send(request);
+                ctx.request_repaint();
+            });
+            requests
+        });
+
+        let ipc_socket = ipc::socket_path(&config);
+        let (sender, dwm_updates) = mpsc::channel();
+        let ctx = cc.egui_ctx.clone();
+        ipc::watch(ipc_socket.clone(), move |state| {
+            let open = sender.send(state).is_ok();
+            ctx.request_repaint();
+            open
+        });
+
+        let (reply_sender, dwm_replies) = mpsc::channel();
+
+        let mut app = Self {
             keybindings,
-            search_text: String::new(),
+            search_text: saved.search,
+            view: View::default(),
+            zoom,
             filtered_keybindings,
+            groups: Vec::new(),
+            group_by: saved.group_by,
+            pair_rows: saved.pair_rows,
+            show_disabled: saved.show_disabled,
+            close_after_run: saved.close_after_run,
+            selected: None,
+            scroll_to_selected: false,
+            collapsed: saved.collapsed.into_iter().collect(),
+            sidebar: saved.sidebar,
+            // Categories the saved order lacks, e.g. from an older version, go last
+            order: saved
+                .order
+                .into_iter()
+                .chain(Category::ALL)
+                .fold(Vec::new(), |mut order, category| {
+                    if !order.contains(&category) {
+                        order.push(category);
+                    }
+                    order
+                }),
+            view_mode: saved.view_mode,
+            sort: saved.sort,
+            card_heights: HashMap::new(),
+            scroll_to_group: None,
+            details: None,
+            show_shortcuts: false,
+            previews: None,
+            missing,
+            show_missing: false,
+            comparison,
+            show_comparison,
+            pending_run: None,
+            focus_search: startup.options.focus_search(),
+            run_status: None,
+            layouts: config.layouts,
+            ipc_socket,
+            dwm_reply: None,
+            dwm_state: None,
+            dwm_updates,
+            dwm_replies,
+            reply_sender,
+            daemon_requests,
+            visible: startup.visible,
+            options: startup.options,
+            last_activity: Instant::now(),
+            had_focus: false,
+        };
+        // eframe restores the saved placement over the one main asked for
+        if !app.options.remembers_placement() {
+            app.place(&cc.egui_ctx);
         }
+        // A search given on the command line wins over the saved one
+        app.apply_view(app.options.view.clone());
+        app
     }
 
-    fn update_filter(&mut self) {
-        self.filtered_keybindings.clear();
+    /// Moves and resizes the window as its options ask.
+    fn place(&self, ctx: &egui::Context) {
+        if let Some(((x, y), (width, height))) = self.options.placement(&self.ipc_socket) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::pos2(
+                x as f32, y as f32,
+            )));
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(Vec2::new(
+                width as f32,
+                height as f32,
+            )));
+        }
+    }
+
+    fn show(&mut self, ctx: &egui::Context) {
+        self.visible = true;
+        if self.options.overlay {
+            // The focused monitor may have changed since the daemon last showed it
+            self.place(ctx);
+            self.last_activity = Instant::now();
+            self.had_focus = false;
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        self.focus_search = self.options.focus_search();
+    }
+
+    /// Hides the daemon's window, ready to be shown again.
+    fn hide(&mut self, ctx: &egui::Context) {
+        self.visible = false;
+        self.pending_run = None;
+        self.details = None;
+        self.show_shortcuts = false;
+        self.show_missing = false;
+        self.show_comparison = false;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+    }
+
+    /// Escape, focus loss and the like: the daemon hides, anything else closes
+    /// the window, which lets eframe save the UI state on the way out.
+    fn dismiss(&mut self, ctx: &egui::Context) {
+        if self.daemon_requests.is_some() {
+            self.hide(ctx);
+        } else {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+    }
+
+    /// Dismisses the overlay when it loses focus, on a key it doesn't use, or
+    /// once it has been idle for the timeout.
+    fn check_overlay_dismissal(&mut self, ctx: &egui::Context) {
+        if !self.options.overlay || !self.visible {
+            return;
+        }
+        let (focused, active, unbound_key) = ctx.input(|i| {
+            let unbound_key = i.events.iter().any(|event| {
+                matches!(event, egui::Event::Key { key, pressed: true, modifiers, .. }
+                    if dismisses_overlay(*key, *modifiers))
+            });
+            (
+                i.viewport().focused,
+                !i.events.is_empty() || i.pointer.is_moving(),
+                unbound_key,
+            )
+        });
+
+        match focused {
+            Some(true) => self.had_focus = true,
+            Some(false) if self.had_focus => return self.dismiss(ctx),
+            _ => {}
+        }
+        if unbound_key {
+            return self.dismiss(ctx);
+        }
+        if active {
+            self.last_activity = Instant::now();
+        }
+        if let Some(timeout) = self.options.timeout {
+            match timeout.checked_sub(self.last_activity.elapsed()) {
+                Some(left) if !left.is_zero() => ctx.request_repaint_after(left),
+                _ => self.dismiss(ctx),
+            }
+        }
+    }
+
+    fn handle_daemon_requests(&mut self, ctx: &egui::Context) {
+        let Some(requests) = &self.daemon_requests else {
+            return;
+        };
+        let requests: Vec<daemon::Request> = requests.try_iter().collect();
+        for request in requests {
+            match request {
+                daemon::Request::Toggle(_) if self.visible => self.hide(ctx),
+                daemon::Request::Toggle(view) | daemon::Request::Show(view) => {
+                    self.apply_view(view);
+                    self.show(ctx);
+                }
+            }
+        }
+    }
+
+    /// Runs a `spawn` binding, asking first when it looks destructive.
+    fn request_run(&mut self, ctx: &egui::Context, keybinding: Keybinding) {
+        let Some(argv) = keybinding.action.as_ref().and_then(|a| a.command()) else {
+            return;
+        };
+        if actions::needs_confirmation(argv) {
+            self.pending_run = Some((keybinding, RowAction::Run));
+        } else {
+            self.run(ctx, &keybinding);
+        }
+    }
+
+    fn run(&mut self, ctx: &egui::Context, keybinding: &Keybinding) {
+        let Some(action) = &keybinding.action else {
+            return;
+        };
+        let Some(argv) = action.command() else {
+            return;
+        };
+        match actions::spawn(argv) {
+            Ok(()) => {
+                self.run_status = Some(Ok(format!(
+                    "Started {}",
+                    action.shell_command().unwrap_or_default()
+                )));
+                if self.close_after_run {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+            }
+            Err(err) => self.run_status = Some(Err(format!("{}: {}", keybinding.function, err))),
+        }
+    }
+
+    /// Calls a dwm-internal binding over IPC in the background and keeps dwm's
+    /// answer for its row.
+    fn run_in_dwm(&mut self, ctx: &egui::Context, keybinding: &Keybinding) {
+        let Some(action) = keybinding.action.clone() else {
+            return;
+        };
+        let combo = keybinding.combo();
+        self.dwm_reply = Some((combo.clone(), Ok(format!("… calling {}", action.function))));
+        let (socket, sender, ctx) = (self.ipc_socket.clone(), self.reply_sender.clone(), ctx.clone());
+        std::thread::spawn(move || {
+            let reply = ipc::IpcClient::connect(&socket)
+                .and_then(|mut client| client.run_command(&action))
+                .map(|_| format!("✓ dwm ran {}", action.function))
+                .map_err(|err| err.to_string());
+            let _ = sender.send((combo, reply));
+            ctx.request_repaint();
+        });
+    }
+
+    /// Hands the combo to a detached `send-keys` process and closes the window so
+    /// the replayed keys reach dwm with the previous client focused.
+    fn trigger(&mut self, ctx: &egui::Context, keybinding: &Keybinding) {
+        let exe = match std::env::current_exe() {
+            Ok(exe) => exe.to_string_lossy().into_owned(),
+            Err(err) => {
+                self.run_status = Some(Err(format!("{}: {}", keybinding.function, err)));
+                return;
+            }
+        };
+        let argv = [
+            exe,
+            "send-keys".to_string(),
+            keybinding.combo(),
+            "--delay".to_string(),
+            TRIGGER_DELAY_MS.to_string(),
+        ];
+        match actions::spawn(&argv) {
+            Ok(()) => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+            Err(err) => self.run_status = Some(Err(format!("{}: {}", keybinding.function, err))),
+        }
+    }
+
+    fn render_run_confirmation(&mut self, ctx: &egui::Context) {
+        let Some((keybinding, row_action)) = self.pending_run.clone() else {
+            return;
+        };
+        let (warning, command) = match row_action {
+            RowAction::Run => (
+                "This command may end your session or processes:".to_string(),
+                keybinding.action.as_ref().and_then(|a| a.shell_command()).unwrap_or_default(),
+            ),
+            RowAction::Dwm | RowAction::Trigger => {
+                let function = keybinding.action.as_ref().map_or(&keybinding.function, |a| &a.function);
+                (format!("This binding calls {} in dwm:", function), keybinding.combo())
+            }
+        };
+
+        let mut decision = None;
+        egui::Window::new(format!("Run {}?", keybinding.function))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .frame(
+                Frame::none()
+                    .fill(MaterialColors::SURFACE_CONTAINER_HIGH)
+                    .rounding(Rounding::same(16.0))
+                    .stroke(Stroke::new(1.0, MaterialColors::OUTLINE_VARIANT))
+                    .inner_margin(Margin::same(20.0)),
+            )
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(warning)
+                        .size(13.0)
+                        .color(MaterialColors::ON_SURFACE_VARIANT),
+                );
+                ui.add_space(8.0);
+                ui.label(
+                    RichText::new(&command)
+                        .size(13.0)
+                        .family(egui::FontFamily::Monospace)
+                        .color(MaterialColors::ON_SURFACE),
+                );
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button(RichText::new("Run").color(MaterialColors::PRIMARY)).clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Enter))
+                    {
+                        decision = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        decision = Some(false);
+                    }
+                });
+            });
+
+        match decision {
+            Some(true) => {
+                self.pending_run = None;
+                match row_action {
+                    RowAction::Run => self.run(ctx, &keybinding),
+                    RowAction::Dwm => self.run_in_dwm(ctx, &keybinding),
+                    RowAction::Trigger => self.trigger(ctx, &keybinding),
+                }
+            }
+            Some(false) => self.pending_run = None,
+            None => {}
+        }
+    }
+
+    /// Chips for the categories and modifier the window was opened with; a click
+    /// drops that filter.
+    fn render_view_filters(&mut self, ui: &mut Ui) {
+        if self.view.categories.is_empty() && self.view.modifier.is_none() {
+            return;
+        }
+        let mut removed_category = None;
+        let mut removed_modifier = false;
+        ui.add_space(8.0);
+        ui.horizontal_wrapped(|ui| {
+            for category in &self.view.categories {
+                let chip = egui::Button::new(
+                    RichText::new(format!("{}  ✕", category.as_str()))
+                        .size(12.0)
+                        .color(self.get_category_color(category)),
+                )
+                .rounding(Rounding::same(12.0));
+                if ui.add(chip).on_hover_text("Stop filtering by this category").clicked() {
+                    removed_category = Some(category.clone());
+                }
+            }
+            if let Some(modifier) = &self.view.modifier {
+                let chip = egui::Button::new(
+                    RichText::new(format!("{}  ✕", modifier))
+                        .size(12.0)
+                        .color(MaterialColors::ON_SURFACE_VARIANT),
+                )
+                .rounding(Rounding::same(12.0));
+                removed_modifier = ui.add(chip).on_hover_text("Stop filtering by this modifier").clicked();
+            }
+        });
+        if let Some(category) = removed_category {
+            self.view.categories.retain(|c| *c != category);
+            self.update_filter();
+        }
+        if removed_modifier {
+            self.view.modifier = None;
+            self.update_filter();
+        }
+    }
 
-        if self.search_text.is_empty() {
-            self.filtered_keybindings = self.keybindings.clone();
+    /// Moves the selection for this frame's navigation keys and takes them out of
+    /// the input so the search field doesn't act on them too. Enter and Ctrl+C
+    /// are returned for the detail pane and the copy after the frame.
+    fn handle_navigation(&mut self, ctx: &egui::Context) -> Navigation {
+        use egui::{Key, Modifiers};
+        // `?` is text while something is being typed into the search
+        let typing = !self.search_text.is_empty() && ctx.memory(|m| m.has_focus(egui::Id::new(SEARCH_ID)));
+        let (down, up, next, previous, enter, copy, help) = ctx.input_mut(|i| {
+            let down = i.consume_key(Modifiers::NONE, Key::ArrowDown) | i.consume_key(Modifiers::CTRL, Key::J);
+            let up = i.consume_key(Modifiers::NONE, Key::ArrowUp) | i.consume_key(Modifiers::CTRL, Key::K);
+            // Shift+Tab first, Tab alone would match it too
+            let previous = i.consume_key(Modifiers::SHIFT, Key::Tab);
+            let next = i.consume_key(Modifiers::NONE, Key::Tab);
+            let enter = i.consume_key(Modifiers::NONE, Key::Enter);
+            let copy = i.events.iter().any(|event| matches!(event, egui::Event::Copy));
+            let typed = i.events.len();
+            i.events.retain(|event| typing || !matches!(event, egui::Event::Text(text) if text == "?"));
+            (down, up, next, previous, enter, copy, i.events.len() != typed)
+        });
+
+        if help {
+            self.show_shortcuts = !self.show_shortcuts;
+        }
+        if self.details.is_some() {
+            return Navigation { enter, copy };
+        }
+
+        let rows = self.rows();
+        let current = self.selected.as_ref().and_then(|selected| rows.iter().position(|row| row == selected));
+        let target = if down {
+            Some(current.map_or(0, |i| (i + 1).min(rows.len().saturating_sub(1))))
+        } else if up {
+            Some(current.map_or(0, |i| i.saturating_sub(1)))
+        } else if next || previous {
+            // First row of the next or previous card, wrapping around
+            let cards: Vec<&Group> = self
+                .groups
+                .iter()
+                .map(|(group, _)| group)
+                .filter(|group| !self.collapsed.contains(group))
+                .collect();
+            let at = current.and_then(|i| cards.iter().position(|group| **group == rows[i].0));
+            let count = cards.len();
+            let index = match (at, next) {
+                _ if count == 0 => None,
+                (None, true) => Some(0),
+                (None, false) => Some(count - 1),
+                (Some(at), true) => Some((at + 1) % count),
+                (Some(at), false) => Some((at + count - 1) % count),
+            };
+            index.and_then(|index| rows.iter().position(|row| row.0 == *cards[index]))
         } else {
-            let search_lower = self.search_text.to_lowercase();
+            None
+        };
+        if let Some(row) = target.and_then(|i| rows.get(i)) {
+            self.selected = Some(row.clone());
+            self.scroll_to_selected = true;
+        }
+
+        if enter {
+            self.details = self.selected_keybinding().cloned();
+        }
+        Navigation { enter: false, copy }
+    }
+
+    /// Every shown row in the order the keyboard walks them: the table's order,
+    /// or the cards' skipping folded ones.
+    fn rows(&self) -> Vec<(Group, usize)> {
+        if self.view_mode == ViewMode::Table {
+            return self.table_rows();
+        }
+        self.groups
+            .iter()
+            .filter(|(group, _)| !self.collapsed.contains(group))
+            .flat_map(|(group, keybindings)| {
+                self.card_rows(keybindings).into_iter().map(|row| (group.clone(), row.lead()))
+            })
+            .collect()
+    }
+
+    /// A card's rows, pairing opposite bindings unless pairs are expanded.
+    fn card_rows(&self, keybindings: &[Keybinding]) -> Vec<pairs::Row> {
+        if self.pair_rows {
+            pairs::rows(keybindings)
+        } else {
+            (0..keybindings.len()).map(pairs::Row::Single).collect()
+        }
+    }
+
+    /// Every shown row in card order, then sorted by the chosen column.
+    fn table_rows(&self) -> Vec<(Group, usize)> {
+        let mut rows: Vec<(Group, usize)> = self
+            .groups
+            .iter()
+            .flat_map(|(group, keybindings)| (0..keybindings.len()).map(|i| (group.clone(), i)))
+            .collect();
+        if let Some((column, ascending)) = self.sort {
+            let keybinding = |row: &(Group, usize)| self.keybinding_at(row).expect("row of a shown card");
+            rows.sort_by(|a, b| {
+                let ordering = column.compare(keybinding(a), keybinding(b));
+                if ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+        rows
+    }
+
+    fn selected_keybinding(&self) -> Option<&Keybinding> {
+        self.keybinding_at(self.selected.as_ref()?)
+    }
+
+    fn keybinding_at(&self, (group, index): &(Group, usize)) -> Option<&Keybinding> {
+        let (_, keybindings) = self.groups.iter().find(|(g, _)| g == group)?;
+        keybindings.get(*index)
+    }
+
+    /// Regroups the filtered bindings into cards after the filter, grouping or
+    /// category order changed.
+    fn regroup(&mut self) {
+        let keybindings = self
+            .order
+            .iter()
+            .filter_map(|category| self.filtered_keybindings.get(category))
+            .flatten();
+        self.groups = groups::group(keybindings, self.group_by, &self.order);
+        self.selected = None;
+    }
+
+    fn perform(&mut self, ctx: &egui::Context, (keybinding, action): (Keybinding, RowAction)) {
+        match action {
+            RowAction::Run => self.request_run(ctx, keybinding),
+            RowAction::Dwm => self.run_in_dwm(ctx, &keybinding),
+            RowAction::Trigger if keybinding.action.as_ref().is_some_and(|a| actions::is_destructive_function(&a.function)) => {
+                self.pending_run = Some((keybinding, action));
+            }
+            RowAction::Trigger => self.trigger(ctx, &keybinding),
+        }
+    }
+
+    /// The open binding's detail pane. Returns its action when its button is
+    /// clicked or `enter` was pressed, closing the pane.
+    fn render_details(&mut self, ctx: &egui::Context, enter: bool) -> Option<(Keybinding, RowAction)> {
+        let keybinding = self.details.clone()?;
+        let accent_color = self.get_category_color(&keybinding.category);
+        let row_action = self.row_action(&keybinding);
+        let mut request = None;
+        let mut close = false;
+        let mut open_file = None;
+
+        let argv = keybinding.action.as_ref().and_then(|action| action.command()).unwrap_or_default();
+        if self.previews.as_ref().is_none_or(|(previewed, _)| previewed.as_slice() != argv) {
+            let files = keybinding.action.as_ref().map(preview::referenced_files).unwrap_or_default();
+            self.previews = Some((argv.to_vec(), files));
+        }
+
+        egui::Window::new(keybinding.combo())
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .frame(
+                Frame::none()
+                    .fill(MaterialColors::SURFACE_CONTAINER_HIGH)
+                    .rounding(Rounding::same(16.0))
+                    .stroke(Stroke::new(1.0, accent_color.gamma_multiply(0.6)))
+                    .inner_margin(Margin::same(20.0)),
+            )
+            .show(ctx, |ui| {
+                ui.set_max_width(480.0);
+                ui.label(
+                    RichText::new(&keybinding.function)
+                        .size(16.0)
+                        .strong()
+                        .color(MaterialColors::PRIMARY),
+                );
+                ui.label(
+                    RichText::new(&keybinding.description)
+                        .size(13.0)
+                        .color(MaterialColors::ON_SURFACE_VARIANT),
+                );
+                ui.add_space(8.0);
+                egui::Grid::new("details").num_columns(2).spacing([12.0, 4.0]).show(ui, |ui| {
+                    let field = |ui: &mut Ui, name: &str, value: String| {
+                        ui.label(RichText::new(name).size(12.0).color(MaterialColors::OUTLINE));
+                        ui.label(
+                            RichText::new(value)
+                                .size(12.0)
+                                .family(egui::FontFamily::Monospace)
+                                .color(MaterialColors::ON_SURFACE),
+                        );
+                        ui.end_row();
+                    };
+                    field(ui, "Category", keybinding.category.as_str().to_string());
+                    if let Some(action) = &keybinding.action {
+                        match action.command() {
+                            Some(argv) => field(ui, "Runs", action.shell_command().unwrap_or_else(|| argv.join(" "))),
+                            None => field(ui, "Argument", action.argument.to_string()),
+                        }
+                        let file = action.file.as_ref().and_then(|file| file.file_name());
+                        let file = file.map_or("config.def.h".into(), |name| name.to_string_lossy());
+                        let lines = if action.end_line > action.line {
+                            format!("{}:{}-{}", file, action.line, action.end_line)
+                        } else {
+                            format!("{}:{}", file, action.line)
+                        };
+                        field(ui, "Source", lines);
+                        if action.disabled {
+                            field(ui, "Status", "disabled, commented out of the config".to_string());
+                        }
+                    }
+                });
+                if let Some(action) = keybinding.action.as_ref().filter(|action| !action.source.is_empty()) {
+                    ui.add_space(8.0);
+                    Frame::none()
+                        .fill(MaterialColors::SURFACE)
+                        .rounding(Rounding::same(8.0))
+                        .inner_margin(Margin::same(8.0))
+                        .show(ui, |ui| {
+                            ui.label(
+                                RichText::new(&action.source)
+                                    .size(12.0)
+                                    .family(egui::FontFamily::Monospace)
+                                    .color(MaterialColors::ON_SURFACE),
+                            );
+                        });
+                }
+                for file in self.previews.iter().flat_map(|(_, files)| files) {
+                    ui.add_space(12.0);
+                    if render_file_preview(ui, file) {
+                        open_file = Some((file.path.clone(), None));
+                    }
+                }
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if let Some((label, hint, action)) = row_action {
+                        if action_button(ui, label, accent_color).on_hover_text(hint).clicked() || enter {
+                            request = Some((keybinding.clone(), action));
+                        }
+                    }
+                    if ui.button("Copy combo").clicked() {
+                        ui.output_mut(|o| o.copied_text = keybinding.combo());
+                    }
+                    if let Some(action) = &keybinding.action {
+                        if let Some(file) = &action.file {
+                            if ui
+                                .button("Edit in $EDITOR")
+                                .on_hover_text(format!("Open {} at line {}", file.display(), action.line))
+                                .clicked()
+                            {
+                                open_file = Some((file.clone(), Some(action.line)));
+                            }
+                        }
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if let Some((path, line)) = open_file {
+            if let Err(err) = actions::open_in_editor(&path, line) {
+                self.run_status = Some(Err(format!("Could not open {}: {}", path.display(), err)));
+            }
+        }
+        if close || request.is_some() {
+            self.details = None;
+        }
+        request
+    }
+
+    /// The `?` help listing the cheatsheet's own shortcuts.
+    fn render_shortcuts(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_shortcuts;
+        egui::Window::new("Keyboard shortcuts")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .frame(
+                Frame::none()
+                    .fill(MaterialColors::SURFACE_CONTAINER_HIGH)
+                    .rounding(Rounding::same(16.0))
+                    .stroke(Stroke::new(1.0, MaterialColors::OUTLINE_VARIANT))
+                    .inner_margin(Margin::same(20.0)),
+            )
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcuts").num_columns(2).spacing([16.0, 6.0]).show(ui, |ui| {
+                    for (keys, action) in SHORTCUTS {
+                        ui.label(
+                            RichText::new(keys)
+                                .size(12.0)
+                                .family(egui::FontFamily::Monospace)
+                                .color(MaterialColors::ON_SURFACE),
+                        );
+                        ui.label(RichText::new(action).size(13.0).color(MaterialColors::ON_SURFACE_VARIANT));
+                        ui.end_row();
+                    }
+                });
+            });
+        self.show_shortcuts = open;
+    }
+
+    /// Missing programs with the combos that run them and how to install them.
+    fn render_missing(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_missing;
+        egui::Window::new("Missing dependencies")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .frame(
+                Frame::none()
+                    .fill(MaterialColors::SURFACE_CONTAINER_HIGH)
+                    .rounding(Rounding::same(16.0))
+                    .stroke(Stroke::new(1.0, MaterialColors::OUTLINE_VARIANT))
+                    .inner_margin(Margin::same(20.0)),
+            )
+            .show(ctx, |ui| {
+                egui::Grid::new("missing").num_columns(3).spacing([16.0, 6.0]).show(ui, |ui| {
+                    for (program, combos) in &self.missing {
+                        ui.label(
+                            RichText::new(program)
+                                .size(12.0)
+                                .family(egui::FontFamily::Monospace)
+                                .color(MaterialColors::ERROR),
+                        );
+                        ui.label(RichText::new(combos.join(", ")).size(13.0).color(MaterialColors::ON_SURFACE_VARIANT));
+                        let hint = deps::install_hint(program).unwrap_or_default();
+                        ui.label(
+                            RichText::new(hint)
+                                .size(12.0)
+                                .family(egui::FontFamily::Monospace)
+                                .color(MaterialColors::ON_SURFACE),
+                        );
+                        ui.end_row();
+                    }
+                });
+            });
+        self.show_missing = open;
+    }
 
-            for (category, keybindings) in &self.keybindings {
-                let filtered: Vec<Keybinding> = keybindings
+    /// The older and newer config side by side, for each binding and setting
+    /// that differs: `+` only in the newer one, `−` only in the older one, `~`
+    /// set differently and `"` described differently.
+    fn render_comparison(&mut self, ctx: &egui::Context) {
+        let Some(diff) = &self.comparison else {
+            return;
+        };
+        let mut open = self.show_comparison;
+        egui::Window::new(format!("{} vs {}", diff.new, diff.old))
+            .id(egui::Id::new("comparison"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .frame(
+                Frame::none()
+                    .fill(MaterialColors::SURFACE_CONTAINER_HIGH)
+                    .rounding(Rounding::same(16.0))
+                    .stroke(Stroke::new(1.0, MaterialColors::OUTLINE_VARIANT))
+                    .inner_margin(Margin::same(20.0)),
+            )
+            .show(ctx, |ui| {
+                let sides = |change: (Option<String>, Option<String>)| (change.0.unwrap_or_default(), change.1.unwrap_or_default());
+                let bindings: Vec<_> = diff
+                    .comparison
+                    .bindings
                     .iter()
-                    .filter(|kb| {
-                        kb.function.to_lowercase().contains(&search_lower)
-                            || kb.description.to_lowercase().contains(&search_lower)
-                            || kb.modifiers.join(" ").to_lowercase().contains(&search_lower)
-                            || kb.key.to_lowercase().contains(&search_lower)
+                    .map(|change| {
+                        let (old, new) = change.sides();
+                        let combo = new.or(old).map(Keybinding::combo).unwrap_or_default();
+                        let (old, new) = sides((old.map(compare::binding_text), new.map(compare::binding_text)));
+                        (change.mark(), combo, old, new)
+                    })
+                    .collect();
+                let settings: Vec<_> = diff
+                    .comparison
+                    .settings
+                    .iter()
+                    .map(|change| {
+                        let (old, new) = change.sides();
+                        let name = new.or(old).map(|setting| setting.name.clone()).unwrap_or_default();
+                        let (old, new) = sides((old.map(|s| s.value.clone()), new.map(|s| s.value.clone())));
+                        (change.mark(), name, old, new)
                     })
-                    .cloned()
                     .collect();
 
-                if !filtered.is_empty() {
-                    self.filtered_keybindings.insert(category.clone(), filtered);
-                }
+                let text = |text: &str, color| RichText::new(text).size(12.0).family(egui::FontFamily::Monospace).color(color);
+                egui::ScrollArea::vertical().max_height(480.0).show(ui, |ui| {
+                    egui::Grid::new("comparison").num_columns(4).spacing([12.0, 4.0]).show(ui, |ui| {
+                        ui.label("");
+                        ui.label("");
+                        for side in [&diff.old, &diff.new] {
+                            ui.label(RichText::new(side).size(12.0).strong().color(MaterialColors::OUTLINE));
+                        }
+                        ui.end_row();
+                        for (title, rows) in [("Bindings", bindings), ("Settings", settings)] {
+                            if rows.is_empty() {
+                                continue;
+                            }
+                            ui.label("");
+                            ui.label(RichText::new(title).size(13.0).strong().color(MaterialColors::ON_SURFACE));
+                            ui.end_row();
+                            for (mark, name, old, new) in rows {
+                                let color = match mark {
+                                    "+" => MaterialColors::PRIMARY,
+                                    "−" => MaterialColors::ERROR,
+                                    _ => MaterialColors::ON_SURFACE_VARIANT,
+                                };
+                                ui.label(RichText::new(mark).size(13.0).strong().color(color));
+                                ui.label(text(&name, MaterialColors::ON_SURFACE));
+                                ui.add(egui::Label::new(text(&old, MaterialColors::ON_SURFACE_VARIANT)).wrap());
+                                ui.add(egui::Label::new(text(&new, MaterialColors::ON_SURFACE_VARIANT)).wrap());
+                                ui.end_row();
+                            }
+                        }
+                    });
+                });
+            });
+        self.show_comparison = open;
+    }
+
+    /// Narrows the window to `view`. Its search replaces the current one only
+    /// when given; categories and modifier always replace theirs.
+    fn apply_view(&mut self, mut view: View) {
+        if let Some(search) = view.search.take() {
+            self.search_text = search;
+        }
+        self.view = view;
+        self.update_filter();
+    }
+
+    fn update_filter(&mut self) {
+        self.filtered_keybindings.clear();
+        let search_lower = self.search_text.to_lowercase();
+
+        for (category, keybindings) in &self.keybindings {
+            let filtered: Vec<Keybinding> = keybindings
+                .iter()
+                .filter(|kb| self.view.shows(kb))
+                .filter(|kb| self.show_disabled || !kb.is_disabled())
+                .filter(|kb| {
+                    search_lower.is_empty()
+                        || kb.function.to_lowercase().contains(&search_lower)
+                        || kb.description.to_lowercase().contains(&search_lower)
+                        || kb.modifiers.join(" ").to_lowercase().contains(&search_lower)
+                        || kb.key.to_lowercase().contains(&search_lower)
+                })
+                .cloned()
+                .collect();
+
+            if !filtered.is_empty() {
+                self.filtered_keybindings.insert(category.clone(), filtered);
             }
         }
+        self.regroup();
     }
 
     fn get_category_color(&self, category: &Category) -> Color32 {
-        match category {
-            Category::Media => Color32::from_rgb(243, 139, 168), // Red
-            Category::Screenshot => Color32::from_rgb(250, 179, 135), // Orange
-            Category::Applications => Color32::from_rgb(249, 226, 175), // Yellow
-            Category::WindowManagement => Color32::from_rgb(166, 227, 161), // Green
-            Category::Layout => Color32::from_rgb(148, 226, 213), // Teal
-            Category::Gaps => Color32::from_rgb(137, 180, 250), // Blue
-            Category::Navigation => Color32::from_rgb(203, 166, 247), // Purple
-            Category::Tags => Color32::from_rgb(245, 194, 231), // Pink
-            Category::System => Color32::from_rgb(235, 160, 172), // Maroon
-            Category::Borders => Color32::from_rgb(180, 190, 254), // Lavender
-        }
+        let [r, g, b] = category.rgb();
+        Color32::from_rgb(r, g, b)
     }
 }
 
 impl eframe::App for CheatsheetApp {
+    fn clear_color(&self, visuals: &egui::Visuals) -> [f32; 4] {
+        if self.options.overlay {
+            // Let the translucent panel decide what shows through
+            [0.0; 4]
+        } else {
+            visuals.panel_fill.to_normalized_gamma_f32()
+        }
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let saved = SavedState {
+            search: self.search_text.clone(),
+            close_after_run: self.close_after_run,
+            zoom: self.zoom,
+            collapsed: self.collapsed.iter().cloned().collect(),
+            sidebar: self.sidebar,
+            order: self.order.clone(),
+            view_mode: self.view_mode,
+            sort: self.sort,
+            group_by: self.group_by,
+            pair_rows: self.pair_rows,
+            show_disabled: self.show_disabled,
+        };
+        eframe::set_value(storage, eframe::APP_KEY, &saved);
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Handle escape key
+        self.zoom = ctx.zoom_factor();
+        self.handle_daemon_requests(ctx);
+
+        // The daemon stays resident; closing the window only hides it
+        let daemon = self.daemon_requests.is_some();
+        if daemon && ctx.input(|i| i.viewport().close_requested()) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.hide(ctx);
+        }
+
+        // Handle escape key; it closes a pending confirmation or popup first
         if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
-            std::process::exit(0);
+            let closed_popup = self.pending_run.take().is_some()
+                || self.details.take().is_some()
+                || std::mem::take(&mut self.show_shortcuts)
+                || std::mem::take(&mut self.show_missing)
+                || std::mem::take(&mut self.show_comparison);
+            if !closed_popup {
+                self.dismiss(ctx);
+            }
+            self.focus_search = self.options.focus_search();
+        }
+        self.check_overlay_dismissal(ctx);
+        // Taken before the search field sees the keys
+        let navigation = if self.pending_run.is_none() {
+            self.handle_navigation(ctx)
+        } else {
+            Navigation::default()
+        };
+
+        while let Ok(state) = self.dwm_updates.try_recv() {
+            self.dwm_state = state;
+        }
+        while let Ok(reply) = self.dwm_replies.try_recv() {
+            self.dwm_reply = Some(reply);
         }
 
-        egui::CentralPanel::default().show(ctx, |ui| {
+        let mut row_request: Option<(Keybinding, RowAction)> = None;
+        let mut toggled = None;
+        let mut measured = Vec::new();
+
+        self.render_sidebar(ctx);
+
+        let mut panel = egui::CentralPanel::default();
+        if self.options.overlay {
+            panel = panel.frame(Frame::central_panel(&ctx.style()).fill(OVERLAY_FILL));
+        }
+        panel.show(ctx, |ui| {
             let available_width = ui.available_width();
             
             // --- Header Section ---
@@ -134,10 +1298,61 @@ impl eframe::App for CheatsheetApp {
                 );
                 ui.add_space(8.0);
                 ui.label(
-                    RichText::new("Press Escape to quit")
+                    RichText::new("Press Escape to quit, ? for keyboard shortcuts")
                         .size(14.0)
                         .color(MaterialColors::ON_SURFACE_VARIANT),
                 );
+                if let Some(state) = &self.dwm_state {
+                    ui.add_space(4.0);
+                    ui.label(
+                        RichText::new(format!("● {}", state.summary()))
+                            .size(13.0)
+                            .color(MaterialColors::PRIMARY),
+                    )
+                    .on_hover_text("Live state from dwm; highlighted bindings apply to it");
+                }
+                if !self.missing.is_empty() {
+                    ui.add_space(4.0);
+                    let text = RichText::new(format!("⚠ {} missing programs", self.missing.len()))
+                        .size(13.0)
+                        .color(MaterialColors::ERROR);
+                    if ui
+                        .add(egui::Button::new(text).frame(false))
+                        .on_hover_text("Programs the bindings run that aren't installed")
+                        .clicked()
+                    {
+                        self.show_missing = !self.show_missing;
+                    }
+                }
+                if let Some(diff) = self.comparison.as_ref().filter(|diff| !diff.comparison.is_empty()) {
+                    ui.add_space(4.0);
+                    let text = RichText::new(format!("⇄ {} differences from {}", diff.comparison.len(), diff.old))
+                        .size(13.0)
+                        .color(MaterialColors::PRIMARY);
+                    if ui
+                        .add(egui::Button::new(text).frame(false))
+                        .on_hover_text("Bindings and settings that differ between the two configs")
+                        .clicked()
+                    {
+                        self.show_comparison = !self.show_comparison;
+                    }
+                }
+                ui.add_space(4.0);
+                ui.checkbox(
+                    &mut self.close_after_run,
+                    RichText::new("Close after running a command")
+                        .size(13.0)
+                        .color(MaterialColors::ON_SURFACE_VARIANT),
+                );
+                match &self.run_status {
+                    Some(Ok(message)) => {
+                        ui.label(RichText::new(message).size(13.0).color(MaterialColors::PRIMARY));
+                    }
+                    Some(Err(message)) => {
+                        ui.label(RichText::new(message).size(13.0).color(MaterialColors::ERROR));
+                    }
+                    None => {}
+                }
             });
             ui.add_space(24.0);
 
@@ -155,6 +1370,7 @@ impl eframe::App for CheatsheetApp {
                             ui.label(RichText::new("🔍").size(16.0).color(MaterialColors::ON_SURFACE_VARIANT));
                             ui.add_space(8.0);
                             let text_edit = egui::TextEdit::singleline(&mut self.search_text)
+                                .id(egui::Id::new(SEARCH_ID))
                                 .frame(false)
                                 .hint_text(RichText::new("Search keybindings...").color(MaterialColors::ON_SURFACE_VARIANT))
                                 .text_color(MaterialColors::ON_SURFACE)
@@ -164,11 +1380,50 @@ impl eframe::App for CheatsheetApp {
                             if response.changed() {
                                 self.update_filter();
                             }
-                            response.request_focus();
+                            if self.details.is_some() || self.show_shortcuts {
+                                response.surrender_focus();
+                            } else if std::mem::take(&mut self.focus_search) {
+                                response.request_focus();
+                            }
                         });
                     });
+                self.render_view_filters(ui);
+            });
+            ui.add_space(16.0);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.selectable_value(&mut self.view_mode, ViewMode::Table, "☰ Table");
+                ui.selectable_value(&mut self.view_mode, ViewMode::Cards, "▦ Cards");
+                ui.add_space(16.0);
+                let group_by = self.group_by;
+                egui::ComboBox::from_id_source("group_by")
+                    .selected_text(group_by.label())
+                    .show_ui(ui, |ui| {
+                        for by in GroupBy::ALL {
+                            ui.selectable_value(&mut self.group_by, by, by.label());
+                        }
+                    });
+                ui.label(RichText::new("Group by").color(MaterialColors::ON_SURFACE_VARIANT));
+                if self.group_by != group_by {
+                    self.regroup();
+                }
+                ui.add_space(16.0);
+                ui.checkbox(&mut self.pair_rows, "Pair ↑/↓")
+                    .on_hover_text("Show bindings that call the same function in opposite directions as one row");
+                ui.add_space(16.0);
+                if ui
+                    .checkbox(&mut self.show_disabled, "Show disabled")
+                    .on_hover_text("Include bindings that are commented out or in an #if 0 or #else branch that isn't compiled")
+                    .changed()
+                {
+                    self.update_filter();
+                }
             });
-            ui.add_space(32.0);
+            ui.add_space(8.0);
+
+            if self.view_mode == ViewMode::Table {
+                self.render_table(ui);
+                return;
+            }
 
             // --- Content Grid ---
             ScrollArea::vertical()
@@ -186,36 +1441,38 @@ impl eframe::App for CheatsheetApp {
                     
                     let column_width = (width - (num_columns as f32 - 1.0) * 16.0) / num_columns as f32;
                     
-                    // Distribute categories into columns
-                    let categories = [
-                        Category::Media,
-                        Category::Screenshot,
-                        Category::Applications,
-                        Category::WindowManagement,
-                        Category::Navigation,
-                        Category::Layout,
-                        Category::Gaps,
-                        Category::Tags,
-                        Category::System,
-                        Category::Borders,
-                    ];
-
-                    let mut columns: Vec<Vec<&Category>> = vec![Vec::new(); num_columns];
-                    for (i, category) in categories.iter().enumerate() {
-                        if self.filtered_keybindings.contains_key(category) {
-                            columns[i % num_columns].push(category);
-                        }
-                    }
+                    // Each card goes into the shortest column, in display order
+                    let cards: Vec<(usize, f32)> = self
+                        .groups
+                        .iter()
+                        .enumerate()
+                        .map(|(i, (group, keybindings))| (i, self.card_height(group, keybindings, column_width)))
+                        .collect();
+                    let columns = pack_columns(&cards, num_columns);
 
                     ui.horizontal_top(|ui| {
-                        for col_idx in 0..num_columns {
+                        for (col_idx, column) in columns.iter().enumerate() {
                             ui.vertical(|ui| {
                                 ui.set_width(column_width);
-                                for category in &columns[col_idx] {
-                                    if let Some(keybindings) = self.filtered_keybindings.get(category) {
-                                        self.render_category_card(ui, category, keybindings);
-                                        ui.add_space(16.0);
+                                for &i in column {
+                                    let (group, keybindings) = &self.groups[i];
+                                    let card = self.render_category_card(ui, group, keybindings);
+                                    if let Some((clicked, action)) = card.clicked {
+                                        row_request = Some((clicked.clone(), action));
                                     }
+                                    if card.header_clicked {
+                                        toggled = Some(group.clone());
+                                    }
+                                    measured.push((
+                                        group.clone(),
+                                        CardHeight {
+                                            width: column_width,
+                                            rows: self.card_rows(keybindings).len(),
+                                            collapsed: self.collapsed.contains(group),
+                                            height: card.height,
+                                        },
+                                    ));
+                                    ui.add_space(16.0);
                                 }
                             });
                             
@@ -228,14 +1485,314 @@ impl eframe::App for CheatsheetApp {
                     ui.add_space(32.0);
                 });
         });
+
+        self.scroll_to_selected = false;
+        self.scroll_to_group = None;
+        for (group, drawn) in measured {
+            // Packed with an estimate or a stale height; pack again with this one
+            let keybindings = self.groups.iter().find(|(g, _)| *g == group).map_or(&[][..], |(_, kb)| kb.as_slice());
+            if (self.card_height(&group, keybindings, drawn.width) - drawn.height).abs() > 1.0 {
+                ctx.request_repaint();
+            }
+            self.card_heights.insert(group, drawn);
+        }
+        if let Some(group) = toggled {
+            if !self.collapsed.remove(&group) {
+                if self.selected.as_ref().is_some_and(|(selected, _)| *selected == group) {
+                    self.selected = None;
+                }
+                self.collapsed.insert(group);
+            }
+        }
+
+        // Ctrl+C copies the selected combo unless the search field copied its text
+        if navigation.copy && ctx.output(|o| o.copied_text.is_empty()) {
+            if let Some(keybinding) = self.selected_keybinding().cloned() {
+                ctx.output_mut(|o| o.copied_text = keybinding.combo());
+                self.run_status = Some(Ok(format!("Copied {}", keybinding.combo())));
+            }
+        }
+
+        if let Some(request) = row_request.or(self.render_details(ctx, navigation.enter)) {
+            self.perform(ctx, request);
+        }
+        self.render_shortcuts(ctx);
+        self.render_missing(ctx);
+        self.render_comparison(ctx);
+        self.render_run_confirmation(ctx);
+    }
+}
+
+/// Keyboard shortcuts listed in the `?` help, as (keys, what they do).
+const SHORTCUTS: [(&str, &str); 8] = [
+    ("↓ / Ctrl+j", "Select the next binding"),
+    ("↑ / Ctrl+k", "Select the previous binding"),
+    ("Tab / Shift+Tab", "Jump to the next or previous category"),
+    ("Enter", "Open the selected binding, Enter again runs it"),
+    ("Ctrl+c", "Copy the selected combo"),
+    ("?", "Show or hide this help"),
+    ("Ctrl + plus / minus", "Zoom"),
+    ("Escape", "Close a popup, then the cheatsheet"),
+];
+
+/// How the bindings are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum ViewMode {
+    /// Category cards packed into columns.
+    Cards,
+    /// One sortable table row per binding.
+    Table,
+}
+
+/// A column of the table view.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum SortColumn {
+    Keys,
+    Function,
+    Description,
+    Category,
+    Action,
+    Line,
+}
+
+impl SortColumn {
+    /// All columns in display order.
+    const ALL: [SortColumn; 6] = [
+        SortColumn::Keys,
+        SortColumn::Function,
+        SortColumn::Description,
+        SortColumn::Category,
+        SortColumn::Action,
+        SortColumn::Line,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            SortColumn::Keys => "Keys",
+            SortColumn::Function => "Function",
+            SortColumn::Description => "Description",
+            SortColumn::Category => "Category",
+            SortColumn::Action => "dwm function",
+            SortColumn::Line => "Line",
+        }
+    }
+
+    fn compare(self, a: &Keybinding, b: &Keybinding) -> Ordering {
+        if self == SortColumn::Line {
+            // Bindings without a source line go last
+            let line = |kb: &Keybinding| kb.action.as_ref().map_or(usize::MAX, |action| action.line);
+            return line(a).cmp(&line(b));
+        }
+        let text = |kb: &Keybinding| {
+            match self {
+                SortColumn::Keys => kb.combo(),
+                SortColumn::Function => kb.function.clone(),
+                SortColumn::Description => kb.description.clone(),
+                SortColumn::Category => kb.category.as_str().to_string(),
+                SortColumn::Action => action_text(kb),
+                SortColumn::Line => unreachable!("compared by number above"),
+            }
+            .to_lowercase()
+        };
+        text(a).cmp(&text(b))
+    }
+}
+
+/// The dwm function a binding calls and its argument, e.g. `incrgaps .i = -3`.
+fn action_text(keybinding: &Keybinding) -> String {
+    match &keybinding.action {
+        Some(action) => format!("{} {}", action.function, action.argument).trim_end().to_string(),
+        None => String::new(),
+    }
+}
+
+/// What happened to a category card this frame.
+struct CardResponse<'a> {
+    /// Binding whose action button was clicked.
+    clicked: Option<(&'a Keybinding, RowAction)>,
+    header_clicked: bool,
+    height: f32,
+}
+
+/// A card's drawn height and what it was drawn with.
+#[derive(Clone, Copy)]
+struct CardHeight {
+    width: f32,
+    rows: usize,
+    collapsed: bool,
+    height: f32,
+}
+
+/// Rough height of a card before it has been drawn, from its rows and how many
+/// lines their descriptions wrap to at `width`.
+fn estimate_card_height(keybindings: &[Keybinding], rows: &[pairs::Row], width: f32, collapsed: bool) -> f32 {
+    const HEADER: f32 = 56.0;
+    const CHAR_WIDTH: f32 = 7.0;
+    if collapsed {
+        return HEADER;
+    }
+    let text_width = (width - 40.0).max(CHAR_WIDTH);
+    let dividers = rows.len().saturating_sub(1) as f32 * 33.0;
+    let rows: f32 = rows
+        .iter()
+        .map(|row| {
+            let kb = &keybindings[row.lead()];
+            let lines = (kb.description.chars().count() as f32 * CHAR_WIDTH / text_width).ceil().max(1.0);
+            34.0 + lines * 17.0
+        })
+        .sum();
+    HEADER + 32.0 + rows + dividers
+}
+
+/// A referenced file's path, whether it exists and its first lines,
+/// highlighted. Returns whether its "Open in $EDITOR" button was clicked.
+fn render_file_preview(ui: &mut Ui, file: &FilePreview) -> bool {
+    let mono = |text: &str| RichText::new(text).size(12.0).family(egui::FontFamily::Monospace);
+    let mut open = false;
+    ui.horizontal(|ui| {
+        ui.label(mono(&file.written).color(MaterialColors::ON_SURFACE))
+            .on_hover_text(file.path.display().to_string());
+        let (status, color) = match &file.contents {
+            Contents::Missing => ("missing".to_string(), MaterialColors::ERROR),
+            Contents::Directory => ("directory".to_string(), MaterialColors::OUTLINE),
+            Contents::Special => ("not a regular file".to_string(), MaterialColors::OUTLINE),
+            Contents::Binary { bytes } => (format!("binary, {} bytes", bytes), MaterialColors::OUTLINE),
+            Contents::Text { .. } => ("exists".to_string(), MaterialColors::OUTLINE),
+        };
+        ui.label(RichText::new(status).size(12.0).color(color));
+        if matches!(file.contents, Contents::Text { .. } | Contents::Binary { .. }) {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                open = ui.button("Open in $EDITOR").clicked();
+            });
+        }
+    });
+    let Contents::Text { text, truncated } = &file.contents else {
+        return open;
+    };
+
+    let mut job = egui::text::LayoutJob::default();
+    for (range, token) in preview::highlight(text, file.language) {
+        let color = match token {
+            preview::Token::Comment => MaterialColors::OUTLINE,
+            preview::Token::String => Color32::from_rgb(166, 227, 161),
+            preview::Token::Keyword => MaterialColors::PRIMARY,
+            preview::Token::Variable => Color32::from_rgb(148, 226, 213),
+            preview::Token::Number => Color32::from_rgb(250, 179, 135),
+            preview::Token::Plain => MaterialColors::ON_SURFACE_VARIANT,
+        };
+        job.append(
+            &text[range],
+            0.0,
+            egui::TextFormat::simple(egui::FontId::monospace(11.0), color),
+        );
+    }
+    Frame::none()
+        .fill(MaterialColors::SURFACE)
+        .rounding(Rounding::same(8.0))
+        .inner_margin(Margin::same(8.0))
+        .show(ui, |ui| {
+            ScrollArea::both()
+                .id_source(&file.written)
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    ui.add(egui::Label::new(job).extend());
+                    if *truncated {
+                        ui.label(mono("…").color(MaterialColors::OUTLINE));
+                    }
+                });
+        });
+    open
+}
+
+/// The saved zoom factor within `ZOOM_RANGE`, so a hand-edited or corrupt state
+/// file can't make the window unusable.
+fn restored_zoom(zoom: f32) -> f32 {
+    if zoom.is_finite() {
+        zoom.clamp(ZOOM_RANGE.0, ZOOM_RANGE.1)
+    } else {
+        1.0
+    }
+}
+
+/// Places cards, in order, into the currently shortest of `columns` columns.
+fn pack_columns(cards: &[(usize, f32)], columns: usize) -> Vec<Vec<usize>> {
+    let mut heights = vec![0.0f32; columns];
+    let mut packed = vec![Vec::new(); columns];
+    for &(card, height) in cards {
+        let (column, _) = heights
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("at least one column");
+        packed[column].push(card);
+        heights[column] += height + 16.0;
+    }
+    packed
+}
+
+/// Navigation keys taken from a frame's input.
+#[derive(Default)]
+struct Navigation {
+    enter: bool,
+    copy: bool,
+}
+
+/// A row action button in `accent_color`.
+fn action_button(ui: &mut Ui, label: &str, accent_color: Color32) -> egui::Response {
+    ui.add(
+        egui::Button::new(RichText::new(label).size(12.0).color(accent_color))
+            .fill(accent_color.gamma_multiply(0.12))
+            .stroke(Stroke::new(1.0, accent_color.gamma_multiply(0.4)))
+            .rounding(Rounding::same(6.0)),
+    )
+}
+
+/// The chip marking a binding that is commented out of the config.
+fn disabled_chip(ui: &mut Ui) {
+    Frame::none()
+        .stroke(Stroke::new(1.0, MaterialColors::OUTLINE))
+        .rounding(Rounding::same(6.0))
+        .inner_margin(Margin::symmetric(6.0, 1.0))
+        .show(ui, |ui| {
+            ui.label(RichText::new("disabled").size(11.0).color(MaterialColors::OUTLINE));
+        });
+}
+
+/// Whether a key press in the overlay falls outside what it uses (typing a
+/// search, editing it, scrolling), like which-key closing on an unknown key.
+fn dismisses_overlay(key: egui::Key, modifiers: egui::Modifiers) -> bool {
+    use egui::Key;
+    if modifiers.ctrl || modifiers.alt {
+        // Text editing and navigation shortcuts stay
+        return !matches!(
+            key,
+            Key::A | Key::C | Key::V | Key::X | Key::Z | Key::J | Key::K | Key::Backspace
+                | Key::Delete | Key::ArrowLeft | Key::ArrowRight
+        );
     }
+    let name = key.name();
+    let function_key = name.len() > 1
+        && name.starts_with('F')
+        && name[1..].chars().all(|c| c.is_ascii_digit());
+    function_key || key == Key::Insert
 }
 
 impl CheatsheetApp {
-    fn render_category_card(&self, ui: &mut Ui, category: &Category, keybindings: &[Keybinding]) {
-        let accent_color = self.get_category_color(category);
-        
-        Frame::none()
+    /// Draws a group's card, folded to its header when collapsed.
+    fn render_category_card<'a>(
+        &self,
+        ui: &mut Ui,
+        group: &Group,
+        keybindings: &'a [Keybinding],
+    ) -> CardResponse<'a> {
+        let [r, g, b] = group.rgb();
+        let accent_color = Color32::from_rgb(r, g, b);
+        let collapsed = self.collapsed.contains(group);
+        let mut clicked = None;
+        let mut header_clicked = false;
+
+        let card = Frame::none()
             .fill(MaterialColors::SURFACE_CONTAINER)
             .rounding(Rounding::same(16.0))
             .stroke(Stroke::new(1.0, MaterialColors::OUTLINE_VARIANT))
@@ -244,13 +1801,14 @@ impl CheatsheetApp {
                 ui.set_width(ui.available_width());
                 
                 // Card Header
-                Frame::none()
+                let bottom = if collapsed { 16.0 } else { 0.0 };
+                let header = Frame::none()
                     .fill(accent_color.gamma_multiply(0.15)) // Tinted background
                     .rounding(Rounding {
                         nw: 16.0,
                         ne: 16.0,
-                        sw: 0.0,
-                        se: 0.0,
+                        sw: bottom,
+                        se: bottom,
                     })
                     .inner_margin(Margin::symmetric(20.0, 16.0))
                     .show(ui, |ui| {
@@ -261,13 +1819,33 @@ impl CheatsheetApp {
                             
                             ui.add_space(12.0);
                             ui.label(
-                                RichText::new(category.as_str())
+                                RichText::new(group.title())
                                     .size(18.0)
                                     .strong()
                                     .color(MaterialColors::ON_SURFACE),
                             );
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                ui.label(
+                                    RichText::new(if collapsed { "▸" } else { "▾" })
+                                        .size(16.0)
+                                        .color(MaterialColors::ON_SURFACE_VARIANT),
+                                );
+                                ui.label(
+                                    RichText::new(keybindings.len().to_string())
+                                        .size(13.0)
+                                        .color(MaterialColors::ON_SURFACE_VARIANT),
+                                );
+                            });
                         });
                     });
+                header_clicked = header
+                    .response
+                    .interact(egui::Sense::click())
+                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                    .clicked();
+                if collapsed {
+                    return;
+                }
 
                 // Card Content
                 ui.allocate_ui(Vec2::new(ui.available_width(), 0.0), |ui| {
@@ -278,40 +1856,311 @@ impl CheatsheetApp {
                     Frame::none()
                         .inner_margin(Margin::symmetric(20.0, 16.0))
                         .show(ui, |ui| {
-                            for (i, keybinding) in keybindings.iter().enumerate() {
-                                if i > 0 {
+                            for (n, row) in self.card_rows(keybindings).into_iter().enumerate() {
+                                if n > 0 {
                                     ui.add_space(16.0);
                                     // Divider
                                     let (rect, _) = ui.allocate_exact_size(Vec2::new(ui.available_width(), 1.0), egui::Sense::hover());
                                     ui.painter().rect_filled(rect, 0.0, MaterialColors::OUTLINE_VARIANT.gamma_multiply(0.3));
                                     ui.add_space(16.0);
                                 }
-                                self.render_keybinding_row(ui, keybinding, accent_color);
+                                let relevant = |keybinding| {
+                                    self.dwm_state
+                                        .as_ref()
+                                        .is_some_and(|state| state.is_relevant(keybinding, &self.layouts))
+                                };
+                                let selected = self
+                                    .selected
+                                    .as_ref()
+                                    .is_some_and(|(selected, i)| selected == group && row.contains(*i));
+                                match row {
+                                    pairs::Row::Single(i) => {
+                                        let keybinding = &keybindings[i];
+                                        if let Some(action) = self.render_keybinding_row(
+                                            ui,
+                                            keybinding,
+                                            accent_color,
+                                            relevant(keybinding),
+                                            selected,
+                                        ) {
+                                            clicked = Some((keybinding, action));
+                                        }
+                                    }
+                                    pairs::Row::Pair { up, down } => {
+                                        let (up, down) = (&keybindings[up], &keybindings[down]);
+                                        let relevant = relevant(up) || relevant(down);
+                                        if let Some((keybinding, action)) =
+                                            self.render_pair_row(ui, up, down, accent_color, relevant, selected)
+                                        {
+                                            clicked = Some((keybinding, action));
+                                        }
+                                    }
+                                }
                             }
                         });
                 });
             });
+        if self.scroll_to_group.as_ref() == Some(group) {
+            card.response.scroll_to_me(Some(egui::Align::TOP));
+        }
+
+        CardResponse {
+            clicked,
+            header_clicked,
+            height: card.response.rect.height(),
+        }
     }
 
-    fn render_keybinding_row(&self, ui: &mut Ui, keybinding: &Keybinding, accent_color: Color32) {
-        ui.vertical(|ui| {
+    /// Height `group`'s card will take at `width`: as last drawn when nothing
+    /// changed since, estimated otherwise.
+    fn card_height(&self, group: &Group, keybindings: &[Keybinding], width: f32) -> f32 {
+        let collapsed = self.collapsed.contains(group);
+        let rows = self.card_rows(keybindings);
+        match self.card_heights.get(group) {
+            Some(last) if last.width == width && last.rows == rows.len() && last.collapsed == collapsed => {
+                last.height
+            }
+            _ => estimate_card_height(keybindings, &rows, width, collapsed),
+        }
+    }
+
+    /// Every shown binding as one row of a sortable, resizable table. A click
+    /// on a row selects it and opens its details.
+    fn render_table(&mut self, ui: &mut Ui) {
+        use egui_extras::{Column, TableBuilder};
+        let rows = self.table_rows();
+        let selected = self.selected.as_ref().and_then(|selected| rows.iter().position(|row| row == selected));
+        let mut clicked = None;
+        let mut sort_by = None;
+
+        let mut table = TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .sense(egui::Sense::click())
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::initial(170.0).at_least(80.0).clip(true))
+            .column(Column::initial(150.0).at_least(60.0).clip(true))
+            .column(Column::remainder().at_least(120.0).clip(true))
+            .column(Column::initial(140.0).at_least(60.0).clip(true))
+            .column(Column::initial(200.0).at_least(80.0).clip(true))
+            .column(Column::initial(60.0).at_least(40.0));
+        if let Some(row) = selected.filter(|_| self.scroll_to_selected) {
+            table = table.scroll_to_row(row, Some(egui::Align::Center));
+        }
+
+        table
+            .header(28.0, |mut header| {
+                for column in SortColumn::ALL {
+                    header.col(|ui| {
+                        let arrow = match self.sort {
+                            Some((sorted, true)) if sorted == column => " ▲",
+                            Some((sorted, false)) if sorted == column => " ▼",
+                            _ => "",
+                        };
+                        let title = RichText::new(format!("{}{}", column.title(), arrow))
+                            .strong()
+                            .color(MaterialColors::ON_SURFACE);
+                        if ui.add(egui::Button::new(title).frame(false)).clicked() {
+                            sort_by = Some(column);
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(26.0, rows.len(), |mut row| {
+                    let index = row.index();
+                    let Some(keybinding) = self.keybinding_at(&rows[index]) else {
+                        return;
+                    };
+                    let category = &keybinding.category;
+                    row.set_selected(selected == Some(index));
+
+                    // Greyed out like in the cards
+                    let dim = |ui: &mut Ui| {
+                        if keybinding.is_disabled() {
+                            ui.multiply_opacity(DISABLED_OPACITY);
+                        }
+                    };
+                    let mono = |text: String| RichText::new(text).family(egui::FontFamily::Monospace);
+                    row.col(|ui| {
+                        dim(ui);
+                        ui.add(egui::Label::new(mono(keybinding.combo()).color(MaterialColors::ON_SURFACE)).truncate());
+                    });
+                    row.col(|ui| {
+                        dim(ui);
+                        ui.add(egui::Label::new(RichText::new(&keybinding.function).color(MaterialColors::PRIMARY)).truncate());
+                    });
+                    row.col(|ui| {
+                        dim(ui);
+                        ui.add(egui::Label::new(&keybinding.description).truncate());
+                    });
+                    row.col(|ui| {
+                        dim(ui);
+                        ui.label(RichText::new(category.as_str()).color(self.get_category_color(category)));
+                    });
+                    row.col(|ui| {
+                        dim(ui);
+                        ui.add(egui::Label::new(mono(action_text(keybinding))).truncate());
+                    });
+                    row.col(|ui| {
+                        dim(ui);
+                        if let Some(action) = &keybinding.action {
+                            ui.label(mono(action.line.to_string()).color(MaterialColors::ON_SURFACE_VARIANT));
+                        }
+                    });
+                    if row.response().clicked() {
+                        clicked = Some(rows[index].clone());
+                    }
+                });
+            });
+
+        // Ascending, then descending, then back to card order
+        if let Some(column) = sort_by {
+            self.sort = match self.sort {
+                Some((sorted, true)) if sorted == column => Some((column, false)),
+                Some((sorted, false)) if sorted == column => None,
+                _ => Some((column, true)),
+            };
+        }
+        if let Some(row) = clicked {
+            self.selected = Some(row);
+            self.details = self.selected_keybinding().cloned();
+        }
+    }
+
+    /// The card list on the left: accent colour and number of bindings the
+    /// current filter shows. A click unfolds that card and scrolls to it, and
+    /// dragging one category onto another reorders the cards; folded down,
+    /// only the colours remain.
+    fn render_sidebar(&mut self, ctx: &egui::Context) {
+        let mut frame = Frame::side_top_panel(&ctx.style()).inner_margin(Margin::symmetric(12.0, 24.0));
+        if self.options.overlay {
+            frame = frame.fill(OVERLAY_FILL);
+        }
+        let collapsed_panel = egui::SidePanel::left("categories_folded").frame(frame).resizable(false).exact_width(40.0);
+        let expanded_panel = egui::SidePanel::left("categories").frame(frame).resizable(false).exact_width(240.0);
+
+        let mut toggle = false;
+        let mut jump = None;
+        let mut moved = None;
+        egui::SidePanel::show_animated_between(ctx, self.sidebar, collapsed_panel, expanded_panel, |ui, how_expanded| {
+            let expanded = how_expanded >= 1.0;
+            let (arrow, hint) = if self.sidebar { ("◂", "Hide categories") } else { ("▸", "Show categories") };
+            toggle = ui
+                .add(egui::Button::new(RichText::new(arrow).size(14.0)).frame(false))
+                .on_hover_text(hint)
+                .clicked();
+            ui.add_space(12.0);
+
+            // Categories are all listed, in their order; other groups as shown
+            let entries: Vec<(Group, usize)> = match self.group_by {
+                GroupBy::Category => self
+                    .order
+                    .iter()
+                    .map(|category| {
+                        let count = self.filtered_keybindings.get(category).map_or(0, Vec::len);
+                        (Group::Category(category.clone()), count)
+                    })
+                    .collect(),
+                _ => self.groups.iter().map(|(group, kbs)| (group.clone(), kbs.len())).collect(),
+            };
+            for (group, count) in entries {
+                let [r, g, b] = group.rgb();
+                let accent_color = Color32::from_rgb(r, g, b);
+                let (accent_color, text_color) = if count == 0 {
+                    (accent_color.gamma_multiply(0.3), MaterialColors::OUTLINE)
+                } else {
+                    (accent_color, MaterialColors::ON_SURFACE)
+                };
+                let row = ui.horizontal(|ui| {
+                    let (rect, _) = ui.allocate_exact_size(Vec2::new(4.0, 20.0), egui::Sense::hover());
+                    ui.painter().rect_filled(rect, 2.0, accent_color);
+                    if expanded {
+                        ui.add_space(8.0);
+                        ui.label(RichText::new(group.title()).size(14.0).color(text_color));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.label(RichText::new(count.to_string()).size(13.0).color(MaterialColors::ON_SURFACE_VARIANT));
+                        });
+                    }
+                });
+                let mut response = row.response.interact(egui::Sense::click_and_drag());
+                if !expanded {
+                    response = response.on_hover_text(format!("{} ({})", group.title(), count));
+                }
+                if let Group::Category(category) = &group {
+                    response.dnd_set_drag_payload(category.clone());
+                    if response.dnd_hover_payload::<Category>().is_some_and(|dragged| *dragged != *category) {
+                        let rect = response.rect;
+                        ui.painter().hline(rect.x_range(), rect.top() - 2.0, Stroke::new(2.0, MaterialColors::PRIMARY));
+                    }
+                    if let Some(dragged) = response.dnd_release_payload::<Category>() {
+                        moved = Some(((*dragged).clone(), category.clone()));
+                    }
+                }
+                if count > 0 && response.on_hover_cursor(egui::CursorIcon::PointingHand).clicked() {
+                    jump = Some(group);
+                }
+                ui.add_space(4.0);
+            }
+        });
+
+        if toggle {
+            self.sidebar = !self.sidebar;
+        }
+        // Dropping a category on another puts it in that one's place
+        if let Some((dragged, target)) = moved {
+            let from = self.order.iter().position(|c| *c == dragged);
+            let to = self.order.iter().position(|c| *c == target);
+            if let (Some(from), Some(to)) = (from, to) {
+                let category = self.order.remove(from);
+                self.order.insert(to, category);
+                self.regroup();
+            }
+        }
+        if let Some(group) = jump {
+            self.collapsed.remove(&group);
+            let first = self
+                .groups
+                .iter()
+                .find(|(g, _)| *g == group)
+                .and_then(|(_, keybindings)| self.card_rows(keybindings).first().map(|row| row.lead()));
+            self.selected = Some((group.clone(), first.unwrap_or(0)));
+            self.scroll_to_group = Some(group);
+        }
+    }
+
+    /// Draws one binding, highlighted when it is `relevant` to dwm's current state
+    /// and outlined when `selected`; returns the action whose button was clicked.
+    fn render_keybinding_row(
+        &self,
+        ui: &mut Ui,
+        keybinding: &Keybinding,
+        accent_color: Color32,
+        relevant: bool,
+        selected: bool,
+    ) -> Option<RowAction> {
+        let mut clicked = None;
+        // Reserve the background so it is painted below the row
+        let highlight = ui.painter().add(egui::Shape::Noop);
+        let row = ui.vertical(|ui| {
+            if keybinding.is_disabled() {
+                ui.multiply_opacity(DISABLED_OPACITY);
+            }
             // Top row: Keys and Function
             ui.horizontal(|ui| {
                 // Keys
                 ui.horizontal_wrapped(|ui| {
                     ui.spacing_mut().item_spacing = Vec2::new(4.0, 4.0);
-                    for (i, modifier) in keybinding.modifiers.iter().enumerate() {
-                        self.render_key_chip(ui, modifier, accent_color);
-                        if i < keybinding.modifiers.len() - 1 || !keybinding.key.is_empty() {
-                            ui.label(RichText::new("+").size(12.0).color(MaterialColors::OUTLINE));
-                        }
-                    }
-                    if !keybinding.key.is_empty() {
-                        self.render_key_chip(ui, &keybinding.key, accent_color);
-                    }
+                    self.render_combo(ui, keybinding, accent_color);
                 });
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if let Some((label, hint, row_action)) = self.row_action(keybinding) {
+                        if action_button(ui, label, accent_color).on_hover_text(hint).clicked() {
+                            clicked = Some(row_action);
+                        }
+                        ui.add_space(8.0);
+                    }
                     ui.label(
                         RichText::new(&keybinding.function)
                             .size(14.0)
@@ -324,12 +2173,210 @@ impl CheatsheetApp {
             ui.add_space(4.0);
             
             // Bottom row: Description
-            ui.label(
-                RichText::new(&keybinding.description)
-                    .size(13.0)
-                    .color(MaterialColors::ON_SURFACE_VARIANT),
-            );
+            ui.horizontal_wrapped(|ui| {
+                if keybinding.is_disabled() {
+                    disabled_chip(ui);
+                }
+                ui.label(
+                    RichText::new(&keybinding.description)
+                        .size(13.0)
+                        .color(MaterialColors::ON_SURFACE_VARIANT),
+                );
+            });
+
+            self.render_missing_chips(ui, keybinding);
+            self.render_dwm_reply(ui, keybinding);
         });
+        self.highlight_row(ui, highlight, &row.response, accent_color, relevant, selected);
+        clicked
+    }
+
+    /// Draws a pair of opposite bindings as one row, e.g. "Inner gaps" with
+    /// both combos; returns the binding whose button was clicked and its action.
+    fn render_pair_row<'a>(
+        &self,
+        ui: &mut Ui,
+        up: &'a Keybinding,
+        down: &'a Keybinding,
+        accent_color: Color32,
+        relevant: bool,
+        selected: bool,
+    ) -> Option<(&'a Keybinding, RowAction)> {
+        let mut clicked = None;
+        let highlight = ui.painter().add(egui::Shape::Noop);
+        let function = up.action.as_ref().map_or("", |action| action.function.as_str());
+        let row = ui.vertical(|ui| {
+            // Pairs are both disabled or both not, see `pairs::rows`
+            if up.is_disabled() {
+                ui.multiply_opacity(DISABLED_OPACITY);
+            }
+            ui.horizontal(|ui| {
+                ui.horizontal_wrapped(|ui| {
+                    ui.spacing_mut().item_spacing = Vec2::new(4.0, 4.0);
+                    let arrow = |text| RichText::new(text).size(14.0).color(accent_color);
+                    self.render_combo(ui, up, accent_color);
+                    ui.label(arrow("↑"));
+                    ui.label(RichText::new("/").size(12.0).color(MaterialColors::OUTLINE));
+                    self.render_combo(ui, down, accent_color);
+                    ui.label(arrow("↓"));
+                });
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    // Right to left, so the ↓ button goes in first
+                    for (keybinding, arrow) in [(down, "↓"), (up, "↑")] {
+                        if let Some((_, hint, row_action)) = self.row_action(keybinding) {
+                            if action_button(ui, arrow, accent_color).on_hover_text(hint).clicked() {
+                                clicked = Some((keybinding, row_action));
+                            }
+                            ui.add_space(4.0);
+                        }
+                    }
+                    ui.add_space(4.0);
+                    ui.label(
+                        RichText::new(pairs::title(function))
+                            .size(14.0)
+                            .strong()
+                            .color(MaterialColors::PRIMARY),
+                    );
+                });
+            });
+
+            ui.add_space(4.0);
+
+            ui.horizontal_wrapped(|ui| {
+                if up.is_disabled() {
+                    disabled_chip(ui);
+                }
+                ui.label(
+                    RichText::new(format!("{} / {}", up.function, down.function))
+                        .size(13.0)
+                        .color(MaterialColors::ON_SURFACE_VARIANT),
+                );
+            });
+
+            self.render_missing_chips(ui, up);
+            self.render_missing_chips(ui, down);
+            self.render_dwm_reply(ui, up);
+            self.render_dwm_reply(ui, down);
+        });
+        self.highlight_row(ui, highlight, &row.response, accent_color, relevant, selected);
+        clicked
+    }
+
+    /// The modifiers and key of `keybinding` as chips joined by `+`.
+    fn render_combo(&self, ui: &mut Ui, keybinding: &Keybinding, accent_color: Color32) {
+        for (i, modifier) in keybinding.modifiers.iter().enumerate() {
+            self.render_key_chip(ui, modifier, accent_color);
+            if i < keybinding.modifiers.len() - 1 || !keybinding.key.is_empty() {
+                ui.label(RichText::new("+").size(12.0).color(MaterialColors::OUTLINE));
+            }
+        }
+        if !keybinding.key.is_empty() {
+            self.render_key_chip(ui, &keybinding.key, accent_color);
+        }
+    }
+
+    /// A warning chip for each program `keybinding` runs that isn't installed.
+    fn render_missing_chips(&self, ui: &mut Ui, keybinding: &Keybinding) {
+        let Some(action) = &keybinding.action else {
+            return;
+        };
+        let missing: Vec<String> = deps::programs(action)
+            .into_iter()
+            .filter(|program| self.missing.contains_key(program))
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        ui.add_space(6.0);
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing = Vec2::new(4.0, 4.0);
+            for program in missing {
+                let hint = match deps::install_hint(&program) {
+                    Some(hint) => format!("{} isn't installed: {}", program, hint),
+                    None => format!("{} isn't installed or not on $PATH", program),
+                };
+                Frame::none()
+                    .fill(MaterialColors::ERROR.gamma_multiply(0.15))
+                    .rounding(Rounding::same(6.0))
+                    .inner_margin(Margin::symmetric(8.0, 2.0))
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(format!("⚠ {}", program)).size(12.0).color(MaterialColors::ERROR));
+                    })
+                    .response
+                    .on_hover_text(hint);
+            }
+        });
+    }
+
+    /// dwm's answer to the last IPC call of `keybinding`, if it was the last one called.
+    fn render_dwm_reply(&self, ui: &mut Ui, keybinding: &Keybinding) {
+        if let Some((combo, reply)) = &self.dwm_reply {
+            if *combo == keybinding.combo() {
+                let (text, color) = match reply {
+                    Ok(message) => (message, MaterialColors::PRIMARY),
+                    Err(message) => (message, MaterialColors::ERROR),
+                };
+                ui.add_space(4.0);
+                ui.label(RichText::new(text).size(12.0).color(color));
+            }
+        }
+    }
+
+    /// Paints a drawn row's background into its reserved `highlight` slot and
+    /// scrolls to it when it was just selected.
+    fn highlight_row(
+        &self,
+        ui: &mut Ui,
+        highlight: egui::layers::ShapeIdx,
+        row: &egui::Response,
+        accent_color: Color32,
+        relevant: bool,
+        selected: bool,
+    ) {
+        if relevant || selected {
+            let fill = if relevant {
+                accent_color.gamma_multiply(0.15)
+            } else {
+                Color32::TRANSPARENT
+            };
+            let stroke = if selected {
+                Stroke::new(1.5, accent_color)
+            } else {
+                Stroke::NONE
+            };
+            ui.painter().set(
+                highlight,
+                egui::epaint::RectShape::new(row.rect.expand(8.0), Rounding::same(8.0), fill, stroke),
+            );
+        }
+        if selected && self.scroll_to_selected {
+            row.scroll_to_me(Some(egui::Align::Center));
+        }
+    }
+
+    /// The button a binding's row offers: run its command, call it over IPC, or
+    /// replay the combo. `None` when there is nothing the cheatsheet can do.
+    fn row_action(&self, keybinding: &Keybinding) -> Option<(&'static str, String, RowAction)> {
+        // A disabled binding isn't what dwm runs: its combo does nothing and its
+        // command or call is only a comment, so it can just be opened in the editor
+        let action = keybinding.action.as_ref().filter(|action| !action.disabled)?;
+        let row_action = match action.shell_command() {
+            Some(command) => ("▶ Run", command, RowAction::Run),
+            None if self.dwm_state.is_some() && ipc::can_run(action) => (
+                "▶ Run",
+                format!("Call {} in dwm over IPC", action.function),
+                RowAction::Dwm,
+            ),
+            None => (
+                "⌨ Trigger",
+                format!("Close the cheatsheet and press {} for dwm", keybinding.combo()),
+                RowAction::Trigger,
+            ),
+        };
+        let replayable =
+            !matches!(row_action.2, RowAction::Trigger) || xtest::parse_combo(&keybinding.combo()).is_ok();
+        replayable.then_some(row_action)
     }
 
     fn render_key_chip(&self, ui: &mut Ui, text: &str, _accent_color: Color32) {
@@ -348,3 +2395,64 @@ impl CheatsheetApp {
             });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybindings::Category;
+    use pairs::Row;
+
+    fn keybinding(description: &str) -> Keybinding {
+        Keybinding {
+            modifiers: vec!["Super".to_string()],
+            key: "a".to_string(),
+            function: "spawn".to_string(),
+            description: description.to_string(),
+            category: Category::Applications,
+            action: None,
+        }
+    }
+
+    #[test]
+    fn card_height_grows_with_rows_and_wrapped_descriptions() {
+        let short = [keybinding("Open"), keybinding("Close")];
+        let long = [keybinding(&"Opens a window ".repeat(10)), keybinding("Close")];
+        let one = estimate_card_height(&short, &[Row::Single(0)], 300.0, false);
+        let two = estimate_card_height(&short, &[Row::Single(0), Row::Single(1)], 300.0, false);
+        let pair = estimate_card_height(&short, &[Row::Pair { up: 0, down: 1 }], 300.0, false);
+        let wrapped = estimate_card_height(&long, &[Row::Single(0)], 300.0, false);
+        let narrow = estimate_card_height(&long, &[Row::Single(0)], 150.0, false);
+
+        assert!(two > one);
+        assert_eq!(pair, one);
+        assert!(wrapped > one);
+        assert!(narrow > wrapped);
+        assert_eq!(estimate_card_height(&long, &[Row::Single(0)], 300.0, true), 56.0);
+        assert!(estimate_card_height(&long, &[Row::Single(0)], 0.0, false).is_finite());
+    }
+
+    #[test]
+    fn restored_zoom_stays_within_the_shortcut_range() {
+        assert_eq!(restored_zoom(1.5), 1.5);
+        assert_eq!(restored_zoom(0.0), 0.2);
+        assert_eq!(restored_zoom(-3.0), 0.2);
+        assert_eq!(restored_zoom(1e9), 5.0);
+        assert_eq!(restored_zoom(f32::NAN), 1.0);
+        assert_eq!(restored_zoom(f32::INFINITY), 1.0);
+    }
+
+    #[test]
+    fn pack_columns_balances_and_keeps_order() {
+        // The two tall cards go to different columns, the short ones fill in
+        assert_eq!(pack_columns(&[(0, 10.0), (1, 9.0), (2, 1.0), (3, 1.0)], 2), [vec![0, 3], vec![1, 2]]);
+        assert_eq!(pack_columns(&[(0, 100.0), (1, 10.0), (2, 10.0), (3, 10.0)], 2), [vec![0], vec![1, 2, 3]]);
+        assert_eq!(pack_columns(&[(4, 1.0), (7, 1.0)], 3), [vec![4], vec![7], vec![]]);
+        assert_eq!(pack_columns(&[(0, 1.0), (1, 1.0)], 1), [vec![0, 1]]);
+
+        // Within every column the cards stay in their original order
+        let cards: Vec<(usize, f32)> = (0..20).map(|i| (i, ((i * 37) % 11) as f32 * 50.0)).collect();
+        for column in pack_columns(&cards, 3) {
+            assert!(column.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", column);
+        }
+    }
+}
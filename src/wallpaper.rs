@@ -0,0 +1,953 @@
+use crate::keybindings::{Category, Keybinding};
+use crate::ui::MaterialColors;
+use ab_glyph::{point, Font as _, FontVec, PxScale, ScaleFont};
+use egui::Color32;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tiny_skia::{
+    FillRule, FilterQuality, Paint, PathBuilder, Pixmap, PixmapPaint, Stroke, Transform,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Png,
+    Svg,
+}
+
+#[derive(Debug, Clone)]
+pub enum Background {
+    Color(Color32),
+    /// PNG image scaled to cover the whole screen.
+    Image(PathBuf),
+}
+
+/// Where the cheatsheet sits on the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position {
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Position {
+    pub fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "center" => Position::Center,
+            "top" => Position::Top,
+            "bottom" => Position::Bottom,
+            "left" => Position::Left,
+            "right" => Position::Right,
+            "top-left" => Position::TopLeft,
+            "top-right" => Position::TopRight,
+            "bottom-left" => Position::BottomLeft,
+            "bottom-right" => Position::BottomRight,
+            _ => return None,
+        })
+    }
+
+    /// Horizontal and vertical alignment factors in `0.0..=1.0`.
+    fn anchor(self) -> (f32, f32) {
+        match self {
+            Position::Center => (0.5, 0.5),
+            Position::Top => (0.5, 0.0),
+            Position::Bottom => (0.5, 1.0),
+            Position::Left => (0.0, 0.5),
+            Position::Right => (1.0, 0.5),
+            Position::TopLeft => (0.0, 0.0),
+            Position::TopRight => (1.0, 0.0),
+            Position::BottomLeft => (0.0, 1.0),
+            Position::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WallpaperOptions {
+    /// One entry per monitor; each produces its own file.
+    pub sizes: Vec<(u32, u32)>,
+    pub output: PathBuf,
+    pub format: Option<Format>,
+    pub background: Background,
+    pub position: Position,
+    /// Opacity of the card layer over the background.
+    pub opacity: f32,
+}
+
+impl Default for WallpaperOptions {
+    fn default() -> Self {
+        Self {
+            sizes: Vec::new(),
+            output: PathBuf::from("dwm-cheatsheet-wallpaper.png"),
+            format: None,
+            background: Background::Color(MaterialColors::SURFACE),
+            position: Position::Center,
+            opacity: 0.9,
+        }
+    }
+}
+
+impl WallpaperOptions {
+    fn format(&self) -> Format {
+        self.format.unwrap_or_else(|| {
+            match self.output.extension().and_then(|ext| ext.to_str()) {
+                Some(ext) if ext.eq_ignore_ascii_case("svg") => Format::Svg,
+                _ => Format::Png,
+            }
+        })
+    }
+
+    /// Output path for `size`, suffixed with the resolution when several are rendered.
+    fn output_for(&self, (width, height): (u32, u32)) -> PathBuf {
+        let extension = match self.format() {
+            Format::Png => "png",
+            Format::Svg => "svg",
+        };
+        let mut output = self.output.with_extension(extension);
+        if self.sizes.len() > 1 {
+            let stem = output
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            output.set_file_name(format!("{}-{}x{}.{}", stem, width, height, extension));
+        }
+        output
+    }
+}
+
+// Card geometry in logical pixels, matching the GUI
+const CARD_WIDTH: f32 = 380.0;
+const CARD_GAP: f32 = 16.0;
+const CARD_RADIUS: f32 = 16.0;
+const HEADER_MARGIN: (f32, f32) = (20.0, 16.0);
+const CONTENT_MARGIN: (f32, f32) = (20.0, 16.0);
+const ROW_GAP: f32 = 12.0;
+const CHIP_MARGIN: (f32, f32) = (8.0, 4.0);
+const CHIP_SPACING: f32 = 4.0;
+const CHIP_RADIUS: f32 = 6.0;
+const HEADER_SIZE: f32 = 18.0;
+const CHIP_SIZE: f32 = 12.0;
+const FUNCTION_SIZE: f32 = 14.0;
+const DESCRIPTION_SIZE: f32 = 13.0;
+
+/// Largest fraction of the screen the cheatsheet may cover.
+const MAX_COVERAGE: f32 = 0.9;
+/// Upper bound for the scale so sparse configs don't render comically large.
+const MAX_SCALE: f32 = 2.0;
+
+/// Renders one wallpaper per requested size and returns the written paths.
+pub fn export(keybindings: &[Keybinding], options: &WallpaperOptions) -> io::Result<Vec<PathBuf>> {
+    let fonts = Fonts::load();
+    let cards = layout_cards(keybindings, &fonts);
+    let background_image = match &options.background {
+        Background::Image(path) => Some(fs::read(path)?),
+        Background::Color(_) => None,
+    };
+
+    let sizes = if options.sizes.is_empty() {
+        vec![(1920, 1080)]
+    } else {
+        options.sizes.clone()
+    };
+
+    let mut written = Vec::new();
+    for size in sizes {
+        let scene = Scene::arrange(&cards, size, options.position);
+        let output = options.output_for(size);
+        match options.format() {
+            Format::Png => {
+                let pixmap = scene.rasterize(&fonts, options, background_image.as_deref())?;
+                write_png(&output, &pixmap)?;
+            }
+            Format::Svg => {
+                fs::write(&output, scene.to_svg(options, background_image.as_deref()))?;
+            }
+        }
+        written.push(output);
+    }
+    Ok(written)
+}
+
+#[derive(Clone, Copy)]
+enum FontKind {
+    Proportional,
+    Monospace,
+}
+
+/// The fonts egui ships with, so the wallpaper uses the same typefaces as the window.
+struct Fonts {
+    proportional: FontVec,
+    monospace: FontVec,
+}
+
+impl Fonts {
+    fn load() -> Self {
+        let definitions = egui::FontDefinitions::default();
+        let font = |name: &str| {
+            let data = &definitions.font_data[name];
+            FontVec::try_from_vec_and_index(data.font.to_vec(), data.index)
+                .expect("egui's bundled fonts are valid")
+        };
+        Self {
+            proportional: font("Ubuntu-Light"),
+            monospace: font("Hack"),
+        }
+    }
+
+    fn get(&self, kind: FontKind) -> &FontVec {
+        match kind {
+            FontKind::Proportional => &self.proportional,
+            FontKind::Monospace => &self.monospace,
+        }
+    }
+
+    fn width(&self, kind: FontKind, size: f32, text: &str) -> f32 {
+        let font = self.get(kind).as_scaled(PxScale::from(size));
+        let mut width = 0.0;
+        let mut previous = None;
+        for c in text.chars() {
+            let glyph = font.glyph_id(c);
+            if let Some(previous) = previous {
+                width += font.kern(previous, glyph);
+            }
+            width += font.h_advance(glyph);
+            previous = Some(glyph);
+        }
+        width
+    }
+
+    fn ascent(&self, kind: FontKind, size: f32) -> f32 {
+        self.get(kind).as_scaled(PxScale::from(size)).ascent()
+    }
+
+    fn line_height(&self, kind: FontKind, size: f32) -> f32 {
+        let font = self.get(kind).as_scaled(PxScale::from(size));
+        font.height() + font.line_gap()
+    }
+
+    fn wrap(&self, kind: FontKind, size: f32, text: &str, width: f32) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut current = String::new();
+        for word in text.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", current, word)
+            };
+            if self.width(kind, size, &candidate) > width && !current.is_empty() {
+                lines.push(std::mem::replace(&mut current, word.to_string()));
+            } else {
+                current = candidate;
+            }
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+        lines
+    }
+}
+
+enum Shape {
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        /// Top-left, top-right, bottom-right, bottom-left.
+        radii: [f32; 4],
+        fill: Color32,
+        stroke: Option<Color32>,
+    },
+    Text {
+        x: f32,
+        baseline: f32,
+        size: f32,
+        font: FontKind,
+        color: Color32,
+        text: String,
+    },
+}
+
+impl Shape {
+    fn translated(&self, dx: f32, dy: f32) -> Shape {
+        match self {
+            Shape::Rect { x, y, width, height, radii, fill, stroke } => Shape::Rect {
+                x: x + dx,
+                y: y + dy,
+                width: *width,
+                height: *height,
+                radii: *radii,
+                fill: *fill,
+                stroke: *stroke,
+            },
+            Shape::Text { x, baseline, size, font, color, text } => Shape::Text {
+                x: x + dx,
+                baseline: baseline + dy,
+                size: *size,
+                font: *font,
+                color: *color,
+                text: text.clone(),
+            },
+        }
+    }
+}
+
+/// A category card laid out at the origin, ready to be placed.
+struct Card {
+    height: f32,
+    shapes: Vec<Shape>,
+}
+
+fn layout_cards(keybindings: &[Keybinding], fonts: &Fonts) -> Vec<Card> {
+    Category::ALL
+        .iter()
+        .filter_map(|category| {
+            let bindings: Vec<&Keybinding> = keybindings
+                .iter()
+                .filter(|kb| kb.category == *category)
+                .collect();
+            (!bindings.is_empty()).then(|| layout_card(category, &bindings, fonts))
+        })
+        .collect()
+}
+
+fn layout_card(category: &Category, keybindings: &[&Keybinding], fonts: &Fonts) -> Card {
+    let [r, g, b] = category.rgb();
+    let accent = Color32::from_rgb(r, g, b);
+    let mut shapes = Vec::new();
+
+    // Header
+    let header_height = 2.0 * HEADER_MARGIN.1 + fonts.line_height(FontKind::Proportional, HEADER_SIZE);
+    shapes.push(Shape::Rect {
+        x: 0.0,
+        y: 0.0,
+        width: CARD_WIDTH,
+        height: header_height,
+        radii: [CARD_RADIUS, CARD_RADIUS, 0.0, 0.0],
+        fill: Color32::from_rgba_unmultiplied(r, g, b, 38),
+        stroke: None,
+    });
+    shapes.push(Shape::Rect {
+        x: HEADER_MARGIN.0,
+        y: (header_height - 20.0) / 2.0,
+        width: 4.0,
+        height: 20.0,
+        radii: [2.0; 4],
+        fill: accent,
+        stroke: None,
+    });
+    shapes.push(Shape::Text {
+        x: HEADER_MARGIN.0 + 16.0,
+        baseline: HEADER_MARGIN.1 + fonts.ascent(FontKind::Proportional, HEADER_SIZE),
+        size: HEADER_SIZE,
+        font: FontKind::Proportional,
+        color: MaterialColors::ON_SURFACE,
+        text: category.as_str().to_string(),
+    });
+
+    // Rows
+    let inner_width = CARD_WIDTH - 2.0 * CONTENT_MARGIN.0;
+    let chip_height = fonts.line_height(FontKind::Monospace, CHIP_SIZE) + 2.0 * CHIP_MARGIN.1;
+    let mut y = header_height + CONTENT_MARGIN.1;
+    for (i, keybinding) in keybindings.iter().enumerate() {
+        if i > 0 {
+            y += ROW_GAP;
+            shapes.push(Shape::Rect {
+                x: CONTENT_MARGIN.0,
+                y,
+                width: inner_width,
+                height: 1.0,
+                radii: [0.0; 4],
+                fill: MaterialColors::OUTLINE_VARIANT.gamma_multiply(0.3),
+                stroke: None,
+            });
+            y += 1.0 + ROW_GAP;
+        }
+        y = layout_row(&mut shapes, keybinding, fonts, y, inner_width, chip_height);
+    }
+    let height = y + CONTENT_MARGIN.1;
+
+    // Card background goes underneath everything else
+    shapes.insert(
+        0,
+        Shape::Rect {
+            x: 0.0,
+            y: 0.0,
+            width: CARD_WIDTH,
+            height,
+            radii: [CARD_RADIUS; 4],
+            fill: MaterialColors::SURFACE_CONTAINER,
+            stroke: Some(MaterialColors::OUTLINE_VARIANT),
+        },
+    );
+
+    Card { height, shapes }
+}
+
+/// Lays out one keybinding row starting at `y` and returns the y below it.
+fn layout_row(
+    shapes: &mut Vec<Shape>,
+    keybinding: &Keybinding,
+    fonts: &Fonts,
+    y: f32,
+    width: f32,
+    chip_height: f32,
+) -> f32 {
+    let mut labels: Vec<(&str, bool)> = Vec::new();
+    for (i, modifier) in keybinding.modifiers.iter().enumerate() {
+        labels.push((modifier, true));
+        if i < keybinding.modifiers.len() - 1 || !keybinding.key.is_empty() {
+            labels.push(("+", false));
+        }
+    }
+    if !keybinding.key.is_empty() {
+        labels.push((&keybinding.key, true));
+    }
+
+    let function_width = fonts.width(FontKind::Proportional, FUNCTION_SIZE, &keybinding.function);
+    let mut x = CONTENT_MARGIN.0;
+    let mut line_y = y;
+    let chip_baseline = CHIP_MARGIN.1 + fonts.ascent(FontKind::Monospace, CHIP_SIZE);
+    for (text, is_chip) in labels {
+        let label_width = if is_chip {
+            fonts.width(FontKind::Monospace, CHIP_SIZE, text) + 2.0 * CHIP_MARGIN.0
+        } else {
+            fonts.width(FontKind::Proportional, CHIP_SIZE, text)
+        };
+        if x + label_width > CONTENT_MARGIN.0 + width && x > CONTENT_MARGIN.0 {
+            x = CONTENT_MARGIN.0;
+            line_y += chip_height + CHIP_SPACING;
+        }
+        if is_chip {
+            shapes.push(Shape::Rect {
+                x,
+                y: line_y,
+                width: label_width,
+                height: chip_height,
+                radii: [CHIP_RADIUS; 4],
+                fill: MaterialColors::SURFACE_CONTAINER_HIGH,
+                stroke: Some(MaterialColors::OUTLINE_VARIANT),
+            });
+            shapes.push(Shape::Text {
+                x: x + CHIP_MARGIN.0,
+                baseline: line_y + chip_baseline,
+                size: CHIP_SIZE,
+                font: FontKind::Monospace,
+                color: MaterialColors::ON_SURFACE,
+                text: text.to_string(),
+            });
+        } else {
+            shapes.push(Shape::Text {
+                x,
+                baseline: line_y + chip_baseline,
+                size: CHIP_SIZE,
+                font: FontKind::Proportional,
+                color: MaterialColors::OUTLINE,
+                text: text.to_string(),
+            });
+        }
+        x += label_width + CHIP_SPACING;
+    }
+    let mut bottom = line_y + chip_height;
+
+    // The function name sits right-aligned next to the keys when there is room
+    let function_inline = line_y == y && x + function_width <= CONTENT_MARGIN.0 + width;
+    let function_baseline = if function_inline {
+        y + (chip_height - fonts.line_height(FontKind::Proportional, FUNCTION_SIZE)) / 2.0
+            + fonts.ascent(FontKind::Proportional, FUNCTION_SIZE)
+    } else {
+        bottom += 4.0 + fonts.line_height(FontKind::Proportional, FUNCTION_SIZE);
+        bottom - fonts.line_height(FontKind::Proportional, FUNCTION_SIZE)
+            + fonts.ascent(FontKind::Proportional, FUNCTION_SIZE)
+    };
+    shapes.push(Shape::Text {
+        x: if function_inline {
+            CONTENT_MARGIN.0 + width - function_width
+        } else {
+            CONTENT_MARGIN.0
+        },
+        baseline: function_baseline,
+        size: FUNCTION_SIZE,
+        font: FontKind::Proportional,
+        color: MaterialColors::PRIMARY,
+        text: keybinding.function.clone(),
+    });
+
+    let line_height = fonts.line_height(FontKind::Proportional, DESCRIPTION_SIZE);
+    let ascent = fonts.ascent(FontKind::Proportional, DESCRIPTION_SIZE);
+    bottom += 4.0;
+    for line in fonts.wrap(FontKind::Proportional, DESCRIPTION_SIZE, &keybinding.description, width) {
+        shapes.push(Shape::Text {
+            x: CONTENT_MARGIN.0,
+            baseline: bottom + ascent,
+            size: DESCRIPTION_SIZE,
+            font: FontKind::Proportional,
+            color: MaterialColors::ON_SURFACE_VARIANT,
+            text: line,
+        });
+        bottom += line_height;
+    }
+    bottom
+}
+
+/// Cards packed into columns, plus where and how large they appear on screen.
+struct Scene {
+    width: u32,
+    height: u32,
+    shapes: Vec<Shape>,
+    scale: f32,
+    offset: (f32, f32),
+}
+
+impl Scene {
+    /// Packs the cards with whichever column count lets them appear largest on
+    /// a screen of `size`, then anchors the result at `position`.
+    fn arrange(cards: &[Card], size: (u32, u32), position: Position) -> Self {
+        let (screen_width, screen_height) = (size.0 as f32, size.1 as f32);
+        let max_width = screen_width * MAX_COVERAGE;
+        let max_height = screen_height * MAX_COVERAGE;
+
+        let mut best: Option<(f32, Vec<Shape>, f32, f32)> = None;
+        for columns in 1..=cards.len().max(1) {
+            let (shapes, width, height) = pack(cards, columns);
+            let scale = (max_width / width).min(max_height / height).min(MAX_SCALE);
+            if best.as_ref().is_none_or(|(best_scale, ..)| scale > *best_scale) {
+                best = Some((scale, shapes, width, height));
+            }
+        }
+        let (scale, shapes, width, height) = best.expect("at least one column count is tried");
+
+        let (ax, ay) = position.anchor();
+        let margin_x = (screen_width - max_width) / 2.0;
+        let margin_y = (screen_height - max_height) / 2.0;
+        let offset = (
+            margin_x + (max_width - width * scale) * ax,
+            margin_y + (max_height - height * scale) * ay,
+        );
+
+        Self {
+            width: size.0,
+            height: size.1,
+            shapes,
+            scale,
+            offset,
+        }
+    }
+
+    fn rasterize(
+        &self,
+        fonts: &Fonts,
+        options: &WallpaperOptions,
+        background_image: Option<&[u8]>,
+    ) -> io::Result<Pixmap> {
+        let mut pixmap = Pixmap::new(self.width, self.height)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid wallpaper size"))?;
+
+        match (&options.background, background_image) {
+            (_, Some(bytes)) => {
+                let image = decode_png(bytes)?;
+                let scale = (self.width as f32 / image.width() as f32)
+                    .max(self.height as f32 / image.height() as f32);
+                let dx = (self.width as f32 - image.width() as f32 * scale) / 2.0;
+                let dy = (self.height as f32 - image.height() as f32 * scale) / 2.0;
+                pixmap.draw_pixmap(
+                    0,
+                    0,
+                    image.as_ref(),
+                    &PixmapPaint {
+                        quality: FilterQuality::Bicubic,
+                        ..Default::default()
+                    },
+                    Transform::from_scale(scale, scale).post_translate(dx, dy),
+                    None,
+                );
+            }
+            (Background::Color(color), None) => pixmap.fill(skia_color(*color)),
+            (Background::Image(_), None) => unreachable!("image backgrounds are read up front"),
+        }
+
+        // Cards are drawn on their own layer so the opacity applies to the group
+        let mut layer = Pixmap::new(self.width, self.height).expect("size checked above");
+        let transform = Transform::from_scale(self.scale, self.scale)
+            .post_translate(self.offset.0, self.offset.1);
+        for shape in &self.shapes {
+            match shape {
+                Shape::Rect { x, y, width, height, radii, fill, stroke } => {
+                    let Some(path) = rounded_rect(*x, *y, *width, *height, *radii) else {
+                        continue;
+                    };
+                    let mut paint = Paint {
+                        anti_alias: true,
+                        ..Default::default()
+                    };
+                    paint.set_color(skia_color(*fill));
+                    layer.fill_path(&path, &paint, FillRule::Winding, transform, None);
+                    if let Some(stroke) = stroke {
+                        paint.set_color(skia_color(*stroke));
+                        layer.stroke_path(&path, &paint, &Stroke::default(), transform, None);
+                    }
+                }
+                Shape::Text { x, baseline, size, font, color, text } => draw_text(
+                    &mut layer,
+                    fonts.get(*font),
+                    *size * self.scale,
+                    self.offset.0 + x * self.scale,
+                    self.offset.1 + baseline * self.scale,
+                    *color,
+                    text,
+                ),
+            }
+        }
+
+        pixmap.draw_pixmap(
+            0,
+            0,
+            layer.as_ref(),
+            &PixmapPaint {
+                opacity: options.opacity,
+                ..Default::default()
+            },
+            Transform::identity(),
+            None,
+        );
+        Ok(pixmap)
+    }
+
+    fn to_svg(&self, options: &WallpaperOptions, background_image: Option<&[u8]>) -> String {
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = self.width,
+            h = self.height
+        );
+
+        match (&options.background, background_image) {
+            (_, Some(bytes)) => {
+                let _ = writeln!(
+                    svg,
+                    r#"  <image width="{}" height="{}" preserveAspectRatio="xMidYMid slice" href="data:image/png;base64,{}"/>"#,
+                    self.width,
+                    self.height,
+                    base64(bytes)
+                );
+            }
+            (Background::Color(color), None) => {
+                let _ = writeln!(svg, r#"  <rect width="100%" height="100%" fill="{}"/>"#, hex(*color));
+            }
+            (Background::Image(_), None) => unreachable!("image backgrounds are read up front"),
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"  <g transform="translate({:.2} {:.2}) scale({:.4})" opacity="{:.2}">"#,
+            self.offset.0, self.offset.1, self.scale, options.opacity
+        );
+        for shape in &self.shapes {
+            match shape {
+                Shape::Rect { x, y, width, height, radii, fill, stroke } => {
+                    let _ = write!(
+                        svg,
+                        r#"    <path d="{}" fill="{}"{}"#,
+                        svg_rounded_rect(*x, *y, *width, *height, *radii),
+                        hex(*fill),
+                        opacity_attr("fill-opacity", *fill)
+                    );
+                    if let Some(stroke) = stroke {
+                        let _ = write!(svg, r#" stroke="{}""#, hex(*stroke));
+                    }
+                    let _ = writeln!(svg, "/>");
+                }
+                Shape::Text { x, baseline, size, font, color, text } => {
+                    let family = match font {
+                        FontKind::Proportional => "Ubuntu, sans-serif",
+                        FontKind::Monospace => "Hack, monospace",
+                    };
+                    let _ = writeln!(
+                        svg,
+                        r#"    <text x="{:.2}" y="{:.2}" font-family="{}" font-size="{}" fill="{}"{}>{}</text>"#,
+                        x,
+                        baseline,
+                        family,
+                        size,
+                        hex(*color),
+                        opacity_attr("fill-opacity", *color),
+                        escape_xml(text)
+                    );
+                }
+            }
+        }
+        svg.push_str("  </g>\n</svg>\n");
+        svg
+    }
+}
+
+/// Places cards into the currently shortest of `columns` columns.
+fn pack(cards: &[Card], columns: usize) -> (Vec<Shape>, f32, f32) {
+    let mut heights = vec![0.0f32; columns];
+    let mut shapes = Vec::new();
+    for card in cards {
+        let (column, top) = heights
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("at least one column");
+        let dx = column as f32 * (CARD_WIDTH + CARD_GAP);
+        shapes.extend(card.shapes.iter().map(|shape| shape.translated(dx, top)));
+        heights[column] = top + card.height + CARD_GAP;
+    }
+    let width = columns as f32 * (CARD_WIDTH + CARD_GAP) - CARD_GAP;
+    let height = heights.iter().copied().fold(0.0, f32::max) - CARD_GAP;
+    (shapes, width, height.max(1.0))
+}
+
+fn rounded_rect(x: f32, y: f32, width: f32, height: f32, radii: [f32; 4]) -> Option<tiny_skia::Path> {
+    // Control point distance approximating a quarter circle with a cubic Bézier
+    const K: f32 = 0.552_284_8;
+    let [tl, tr, br, bl] = radii;
+    let (right, bottom) = (x + width, y + height);
+    let mut pb = PathBuilder::new();
+    pb.move_to(x + tl, y);
+    pb.line_to(right - tr, y);
+    pb.cubic_to(right - tr + tr * K, y, right, y + tr - tr * K, right, y + tr);
+    pb.line_to(right, bottom - br);
+    pb.cubic_to(right, bottom - br + br * K, right - br + br * K, bottom, right - br, bottom);
+    pb.line_to(x + bl, bottom);
+    pb.cubic_to(x + bl - bl * K, bottom, x, bottom - bl + bl * K, x, bottom - bl);
+    pb.line_to(x, y + tl);
+    pb.cubic_to(x, y + tl - tl * K, x + tl - tl * K, y, x + tl, y);
+    pb.close();
+    pb.finish()
+}
+
+fn svg_rounded_rect(x: f32, y: f32, width: f32, height: f32, radii: [f32; 4]) -> String {
+    let [tl, tr, br, bl] = radii;
+    let (right, bottom) = (x + width, y + height);
+    format!(
+        "M{:.2} {:.2}H{:.2}A{tr} {tr} 0 0 1 {:.2} {:.2}V{:.2}A{br} {br} 0 0 1 {:.2} {:.2}H{:.2}A{bl} {bl} 0 0 1 {:.2} {:.2}V{:.2}A{tl} {tl} 0 0 1 {:.2} {:.2}Z",
+        x + tl, y, right - tr, right, y + tr, bottom - br, right - br, bottom,
+        x + bl, x, bottom - bl, y + tl, x + tl, y,
+    )
+}
+
+/// Rasterizes `text` with ab_glyph, blending coverage into the premultiplied pixmap.
+fn draw_text(pixmap: &mut Pixmap, font: &FontVec, size: f32, x: f32, baseline: f32, color: Color32, text: &str) {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    let (width, height) = (pixmap.width() as i32, pixmap.height() as i32);
+    let pixels = pixmap.pixels_mut();
+
+    let mut caret = x;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            caret += scaled.kern(previous, id);
+        }
+        let glyph = id.with_scale_and_position(PxScale::from(size), point(caret, baseline));
+        caret += scaled.h_advance(id);
+        previous = Some(id);
+
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i32 + gx as i32;
+            let py = bounds.min.y as i32 + gy as i32;
+            if px < 0 || py < 0 || px >= width || py >= height {
+                return;
+            }
+            let pixel = &mut pixels[(py * width + px) as usize];
+            let alpha = coverage.clamp(0.0, 1.0) * a as f32 / 255.0;
+            let blend = |src: u8, dst: u8| (src as f32 * alpha + dst as f32 * (1.0 - alpha)).round() as u8;
+            *pixel = tiny_skia::PremultipliedColorU8::from_rgba(
+                blend(r, pixel.red()),
+                blend(g, pixel.green()),
+                blend(b, pixel.blue()),
+                blend(255, pixel.alpha()),
+            )
+            .unwrap_or(*pixel);
+        });
+    }
+}
+
+fn decode_png(bytes: &[u8]) -> io::Result<Pixmap> {
+    let invalid = |err: png::DecodingError| io::Error::new(io::ErrorKind::InvalidData, err);
+    let mut decoder = png::Decoder::new(io::Cursor::new(bytes));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(invalid)?;
+    let mut buffer = vec![0; reader.output_buffer_size().unwrap_or(0)];
+    let info = reader.next_frame(&mut buffer).map_err(invalid)?;
+
+    let mut rgba = Vec::with_capacity(info.width as usize * info.height as usize * 4);
+    for pixel in buffer[..info.buffer_size()].chunks_exact(info.color_type.samples()) {
+        let [r, g, b, a] = match *pixel {
+            [r, g, b, a] => [r, g, b, a],
+            [r, g, b] => [r, g, b, 255],
+            [l, a] => [l, l, l, a],
+            [l] => [l, l, l, 255],
+            _ => unreachable!("8-bit PNG pixels have 1 to 4 samples"),
+        };
+        let premultiply = |c: u8| ((c as u16 * a as u16 + 127) / 255) as u8;
+        rgba.extend_from_slice(&[premultiply(r), premultiply(g), premultiply(b), a]);
+    }
+
+    let size = tiny_skia::IntSize::from_wh(info.width, info.height)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "empty background image"))?;
+    Pixmap::from_vec(rgba, size)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "unsupported background image"))
+}
+
+fn write_png(path: &Path, pixmap: &Pixmap) -> io::Result<()> {
+    let mut rgba = Vec::with_capacity(pixmap.data().len());
+    for pixel in pixmap.pixels() {
+        let color = pixel.demultiply();
+        rgba.extend_from_slice(&[color.red(), color.green(), color.blue(), color.alpha()]);
+    }
+
+    let file = io::BufWriter::new(fs::File::create(path)?);
+    let mut encoder = png::Encoder::new(file, pixmap.width(), pixmap.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&rgba).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+fn skia_color(color: Color32) -> tiny_skia::Color {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    tiny_skia::Color::from_rgba8(r, g, b, a)
+}
+
+fn hex(color: Color32) -> String {
+    let [r, g, b, _] = color.to_srgba_unmultiplied();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn opacity_attr(name: &str, color: Color32) -> String {
+    match color.a() {
+        255 => String::new(),
+        a => format!(r#" {}="{:.3}""#, name, a as f32 / 255.0),
+    }
+}
+
+/// Parses `#rrggbb` (the `#` is optional).
+pub fn parse_color(value: &str) -> Option<Color32> {
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color32::from_rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_pads_partial_chunks() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"M"), "TQ==");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"Many"), "TWFueQ==");
+        assert_eq!(base64(&[0xff, 0xfe, 0xfd]), "//79");
+    }
+
+    #[test]
+    fn colors_are_six_hex_digits() {
+        assert_eq!(parse_color("#1e1e2e"), Some(Color32::from_rgb(0x1e, 0x1e, 0x2e)));
+        assert_eq!(parse_color("FFa500"), Some(Color32::from_rgb(0xff, 0xa5, 0x00)));
+        for value in ["", "#fff", "#1e1e2e00", "#12345g", "#1234\u{e9}"] {
+            assert_eq!(parse_color(value), None, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn positions_are_kebab_case() {
+        assert_eq!(Position::parse("center"), Some(Position::Center));
+        assert_eq!(Position::parse("top-left"), Some(Position::TopLeft));
+        assert_eq!(Position::parse("bottom-right"), Some(Position::BottomRight));
+        assert_eq!(Position::parse("top_left"), None);
+        assert_eq!(Position::parse("Center"), None);
+    }
+
+    /// The right and bottom edge of the furthest card.
+    fn extent(shapes: &[Shape]) -> (f32, f32) {
+        shapes.iter().fold((0.0, 0.0), |(right, bottom), shape| match shape {
+            Shape::Rect { x, y, width, height, .. } => (right.max(x + width), bottom.max(y + height)),
+            Shape::Text { .. } => (right, bottom),
+        })
+    }
+
+    #[test]
+    fn arranged_scene_stays_within_the_coverage() {
+        let fonts = Fonts::load();
+        let keybinding = |category, key: &str| Keybinding {
+            modifiers: vec!["Super".to_string(), "Shift".to_string()],
+            key: key.to_string(),
+            function: "spawn".to_string(),
+            description: "Launches something with a fairly long description".to_string(),
+            category,
+            action: None,
+        };
+        let few = vec![keybinding(Category::Applications, "Return")];
+        let many: Vec<Keybinding> = Category::ALL
+            .iter()
+            .flat_map(|category| (0..12).map(move |i| keybinding(category.clone(), &format!("F{}", i))))
+            .collect();
+
+        for keybindings in [&few, &many] {
+            let cards = layout_cards(keybindings, &fonts);
+            for size in [(1920, 1080), (1080, 1920), (800, 600), (3840, 2160)] {
+                for position in [Position::Center, Position::TopLeft, Position::BottomRight] {
+                    let scene = Scene::arrange(&cards, size, position);
+                    let (screen_width, screen_height) = (size.0 as f32, size.1 as f32);
+                    let (left, top) = scene.offset;
+                    let (width, height) = extent(&scene.shapes);
+                    let (right, bottom) = (left + width * scene.scale, top + height * scene.scale);
+                    let margin_x = screen_width * (1.0 - MAX_COVERAGE) / 2.0;
+                    let margin_y = screen_height * (1.0 - MAX_COVERAGE) / 2.0;
+                    assert!(scene.scale <= MAX_SCALE);
+                    assert!(left >= margin_x - 0.5 && right <= screen_width - margin_x + 0.5, "{:?} {:?}", size, position);
+                    assert!(top >= margin_y - 0.5 && bottom <= screen_height - margin_y + 0.5, "{:?} {:?}", size, position);
+                }
+            }
+        }
+    }
+}
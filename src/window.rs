@@ -0,0 +1,35 @@
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use std::error::Error;
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, PropMode};
+use x11rb::wrapper::ConnectionExt as _;
+
+/// Class and instance dwm's rules match when nothing else is asked for.
+pub const DEFAULT_CLASS: &str = "dwm-cheatsheet";
+
+/// Sets `WM_CLASS` on the cheatsheet's X11 window. eframe leaves it to winit,
+/// which derives it from argv[0], so dwm rules would break when the binary is
+/// renamed or run through a wrapper.
+///
+/// Must run before the window is mapped, which is why it is done while eframe
+/// still keeps the window hidden for its first frame. Wayland windows are left
+/// alone; their app id is set through the viewport builder.
+pub fn set_wm_class(window: &impl HasWindowHandle, instance: &str, class: &str) -> Result<(), Box<dyn Error>> {
+    let id = match window.window_handle()?.as_raw() {
+        RawWindowHandle::Xlib(handle) => handle.window as u32,
+        RawWindowHandle::Xcb(handle) => handle.window.get(),
+        _ => return Ok(()),
+    };
+
+    let (conn, _) = x11rb::connect(None)?;
+    let value = format!("{}\0{}\0", instance, class);
+    conn.change_property8(
+        PropMode::REPLACE,
+        id,
+        AtomEnum::WM_CLASS,
+        AtomEnum::STRING,
+        value.as_bytes(),
+    )?;
+    // Round trip so the property is set before winit maps the window
+    conn.get_input_focus()?.reply()?;
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+use x11rb::connection::{Connection, RequestConnection as _};
+use x11rb::protocol::xproto::{self, ConnectionExt as _};
+use x11rb::protocol::xtest::{self, ConnectionExt as _};
+
+/// X11 keysym value for a keysym name as written in `config.h` (without `XK_`).
+pub fn keysym(name: &str) -> Option<u32> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        // Latin-1 keysyms equal their character; dwm binds the lowercase letter
+        if c.is_ascii_graphic() {
+            return Some(c.to_ascii_lowercase() as u32);
+        }
+    }
+
+    let named = match name.to_ascii_lowercase().as_str() {
+        "space" => 0x0020,
+        "exclam" => 0x0021,
+        "apostrophe" => 0x0027,
+        "comma" => 0x002c,
+        "minus" => 0x002d,
+        "period" => 0x002e,
+        "slash" => 0x002f,
+        "semicolon" => 0x003b,
+        "equal" => 0x003d,
+        "bracketleft" => 0x005b,
+        "backslash" => 0x005c,
+        "bracketright" => 0x005d,
+        "grave" => 0x0060,
+        "backspace" => 0xff08,
+        "tab" => 0xff09,
+        "return" => 0xff0d,
+        "pause" => 0xff13,
+        "print" => 0xff61,
+        "escape" => 0xff1b,
+        "delete" => 0xffff,
+        "home" => 0xff50,
+        "left" => 0xff51,
+        "up" => 0xff52,
+        "right" => 0xff53,
+        "down" => 0xff54,
+        "prior" | "page_up" => 0xff55,
+        "next" | "page_down" => 0xff56,
+        "end" => 0xff57,
+        "insert" => 0xff63,
+        "xf86monbrightnessup" => 0x1008ff02,
+        "xf86monbrightnessdown" => 0x1008ff03,
+        "xf86audiolowervolume" => 0x1008ff11,
+        "xf86audiomute" => 0x1008ff12,
+        "xf86audioraisevolume" => 0x1008ff13,
+        "xf86audioplay" => 0x1008ff14,
+        "xf86audiostop" => 0x1008ff15,
+        "xf86audioprev" => 0x1008ff16,
+        "xf86audionext" => 0x1008ff17,
+        "xf86audiomicmute" => 0x1008ffb2,
+        function => {
+            // F1 .. F35
+            let n: u32 = function.strip_prefix('f')?.parse().ok()?;
+            return (1..=35).contains(&n).then(|| 0xffbe + n - 1);
+        }
+    };
+    Some(named)
+}
+
+/// Index into the X modifier mapping for a modifier name as shown in the cheatsheet.
+fn modifier_index(name: &str) -> Option<usize> {
+    match name {
+        "Shift" => Some(0),
+        "Lock" => Some(1),
+        "Ctrl" => Some(2),
+        "Alt" | "Mod1" => Some(3),
+        "Mod2" => Some(4),
+        "Mod3" => Some(5),
+        "Super" | "Mod4" => Some(6),
+        "Mod5" => Some(7),
+        _ => None,
+    }
+}
+
+/// Splits `Super+Shift+f` into modifier mapping indices and a keysym.
+pub fn parse_combo(combo: &str) -> Result<(Vec<usize>, u32), String> {
+    let mut parts: Vec<&str> = combo.split('+').map(str::trim).collect();
+    let key = parts
+        .pop()
+        .filter(|key| !key.is_empty())
+        .ok_or("empty key combination")?;
+    let keysym = keysym(key).ok_or_else(|| format!("unknown keysym '{}'", key))?;
+    let modifiers = parts
+        .iter()
+        .map(|m| modifier_index(m).ok_or_else(|| format!("unknown modifier '{}'", m)))
+        .collect::<Result<_, _>>()?;
+    Ok((modifiers, keysym))
+}
+
+/// Presses and releases `combo` through the XTEST extension after `delay`, so the
+/// window manager sees it exactly like a physical key press.
+pub fn send_keys(combo: &str, delay: Duration) -> Result<(), Box<dyn Error>> {
+    let (modifiers, keysym) = parse_combo(combo)?;
+    thread::sleep(delay);
+
+    let (conn, _) = x11rb::connect(None)?;
+    if conn
+        .extension_information(xtest::X11_EXTENSION_NAME)?
+        .is_none()
+    {
+        return Err("the X server does not support the XTEST extension".into());
+    }
+
+    let keycode = keycode_for(&conn, keysym)?
+        .ok_or_else(|| format!("no keycode produces keysym {:#x}", keysym))?;
+    let modifier_map = conn.get_modifier_mapping()?.reply()?;
+    let per_modifier = modifier_map.keycodes_per_modifier() as usize;
+    let modifier_keycodes = modifiers
+        .iter()
+        .map(|&index| {
+            modifier_map.keycodes[index * per_modifier..(index + 1) * per_modifier]
+                .iter()
+                .copied()
+                .find(|&keycode| keycode != 0)
+                .ok_or_else(|| format!("no key is mapped to modifier {}", index))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let fake = |event: u8, keycode: u8| {
+        conn.xtest_fake_input(event, keycode, x11rb::CURRENT_TIME, x11rb::NONE, 0, 0, 0)
+    };
+    for &modifier in &modifier_keycodes {
+        fake(xproto::KEY_PRESS_EVENT, modifier)?;
+    }
+    fake(xproto::KEY_PRESS_EVENT, keycode)?;
+    fake(xproto::KEY_RELEASE_EVENT, keycode)?;
+    for &modifier in modifier_keycodes.iter().rev() {
+        fake(xproto::KEY_RELEASE_EVENT, modifier)?;
+    }
+
+    // Round trip so every request is processed before we disconnect
+    conn.get_input_focus()?.reply()?;
+    Ok(())
+}
+
+/// The keycode whose unshifted keysym is `keysym`, which is how dwm matches keys.
+fn keycode_for(conn: &impl Connection, keysym: u32) -> Result<Option<u8>, Box<dyn Error>> {
+    let setup = conn.setup();
+    let (min, max) = (setup.min_keycode, setup.max_keycode);
+    let mapping = conn.get_keyboard_mapping(min, max - min + 1)?.reply()?;
+    let per_keycode = mapping.keysyms_per_keycode as usize;
+    if per_keycode == 0 {
+        return Ok(None);
+    }
+
+    Ok(mapping
+        .keysyms
+        .chunks(per_keycode)
+        .position(|syms| syms[0] == keysym)
+        .map(|offset| min + offset as u8))
+}
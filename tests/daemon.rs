@@ -0,0 +1,172 @@
+//! Talks the daemon's socket protocol: `toggle` and `show` against a fake daemon,
+//! so no display is needed, and raw requests against a real daemon on Xvfb.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const BIN: &str = env!("CARGO_BIN_EXE_dwm-cheatsheet");
+
+/// A private `XDG_RUNTIME_DIR`, where the cheatsheet puts its socket, removed
+/// when dropped.
+struct RuntimeDir(PathBuf);
+
+impl RuntimeDir {
+    fn new(name: &str) -> RuntimeDir {
+        let dir = std::env::temp_dir().join(format!("dwm-cheatsheet-daemon-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        RuntimeDir(dir)
+    }
+
+    fn socket(&self) -> PathBuf {
+        self.0.join("dwm-cheatsheet.sock")
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(BIN);
+        command
+            .args(args)
+            .env("XDG_RUNTIME_DIR", &self.0)
+            .env("HOME", &self.0)
+            .env_remove("WAYLAND_DISPLAY");
+        command
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        self.command(args).env_remove("DISPLAY").output().expect("run dwm-cheatsheet")
+    }
+}
+
+impl Drop for RuntimeDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Stands in for a running daemon: records each request line and answers `reply`.
+fn fake_daemon(dir: &RuntimeDir, reply: &'static str) -> Arc<Mutex<Vec<serde_json::Value>>> {
+    let listener = UnixListener::bind(dir.socket()).unwrap();
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&received);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = stream.unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            log.lock().unwrap().push(serde_json::from_str(&line).unwrap());
+            (&stream).write_all(reply.as_bytes()).unwrap();
+        }
+    });
+    received
+}
+
+#[test]
+fn toggle_and_show_are_handed_to_a_running_daemon() {
+    let dir = RuntimeDir::new("forward");
+    let received = fake_daemon(&dir, "ok\n");
+
+    for args in [&["toggle"][..], &["show", "--search", "x", "--overlay"]] {
+        let output = dir.run(args);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    let received = received.lock().unwrap();
+    assert_eq!(received[0]["request"]["Toggle"]["search"], serde_json::Value::Null);
+    assert_eq!(received[0]["startup"], serde_json::json!([]));
+    assert_eq!(received[1]["request"]["Show"]["search"], "x");
+    assert_eq!(received[1]["startup"], serde_json::json!(["--overlay"]));
+}
+
+#[test]
+fn a_refused_request_fails_with_the_daemons_reason() {
+    let dir = RuntimeDir::new("refused");
+    fake_daemon(&dir, "error: the running daemon wasn't started with --overlay\n");
+
+    let output = dir.run(&["show", "--overlay"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("wasn't started with --overlay"), "{}", stderr);
+}
+
+/// An Xvfb server that is killed when dropped.
+struct Xvfb {
+    child: Child,
+    display: String,
+}
+
+impl Xvfb {
+    fn start() -> Option<Xvfb> {
+        let mut child = Command::new("Xvfb")
+            .args(["-displayfd", "1", "-nolisten", "tcp", "-screen", "0", "640x480x24"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().expect("stdout is piped"))
+            .read_line(&mut line)
+            .expect("read display number from Xvfb");
+        let display = format!(":{}", line.trim());
+        Some(Xvfb { child, display })
+    }
+}
+
+impl Drop for Xvfb {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Sends `line` on a fresh connection and returns the daemon's reply.
+fn request(dir: &RuntimeDir, line: &str) -> String {
+    let mut stream = UnixStream::connect(dir.socket()).expect("connect to the daemon");
+    stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    stream.write_all(line.as_bytes()).unwrap();
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).unwrap();
+    reply
+}
+
+#[test]
+#[ignore = "needs Xvfb; run with cargo test -- --ignored"]
+fn daemon_answers_requests_on_its_socket() {
+    let xvfb = Xvfb::start().expect("start Xvfb");
+    let dir = RuntimeDir::new("real");
+    let mut daemon = dir
+        .command(&["daemon"])
+        .env("DISPLAY", &xvfb.display)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("start the daemon");
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while UnixStream::connect(dir.socket()).is_err() {
+        assert!(Instant::now() < deadline, "the daemon never listened");
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    // A client that never finishes its line mustn't hold up the ones after it
+    let _stalled = UnixStream::connect(dir.socket()).unwrap();
+
+    let view = r#"{"search":null,"categories":[],"modifier":null}"#;
+    assert_eq!(request(&dir, &format!("{{\"request\":{{\"Toggle\":{}}}}}\n", view)), "ok\n");
+    let search = r#"{"search":"x","categories":[],"modifier":null}"#;
+    assert_eq!(request(&dir, &format!("{{\"request\":{{\"Show\":{}}}}}\n", search)), "ok\n");
+    assert!(request(&dir, "not json\n").starts_with("error: "));
+    let overlay = format!("{{\"request\":{{\"Show\":{}}},\"startup\":[\"--overlay\"]}}\n", view);
+    assert!(request(&dir, &overlay).starts_with("error: the running daemon wasn't started with --overlay"));
+
+    let output = dir.command(&["show", "--search", "x"]).env("DISPLAY", &xvfb.display).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let _ = daemon.kill();
+    let _ = daemon.wait();
+}
@@ -0,0 +1,124 @@
+//! Compares small configs with `dwm-cheatsheet diff`, from files and from git.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const BIN: &str = env!("CARGO_BIN_EXE_dwm-cheatsheet");
+
+const OLD: &str = "#define MODKEY Mod4Mask\n\
+    static const unsigned int borderpx = 3;\n\
+    static const Key keys[] = {\n\
+        {MODKEY, XK_b, togglebar, {0}},\n\
+        {MODKEY | ShiftMask, XK_q, killclient, {0}},\n\
+        {MODKEY, XK_minus, incrgaps, {.i = -1}},\n\
+    };\n";
+
+/// `OLD` with Super+b gone, Super+Shift+q rebound (its modifiers and key
+/// written differently) and a binding and a setting added.
+const NEW: &str = "#define MODKEY Mod4Mask\n\
+    static const unsigned int borderpx = 2;\n\
+    static const unsigned int gappih = 10;\n\
+    static const Key keys[] = {\n\
+        {ShiftMask | MODKEY, XK_Q, quit, {0}},\n\
+        {MODKEY, XK_minus, incrgaps, {.i = -1}},\n\
+        {MODKEY, XK_f, togglefloating, {0}},\n\
+    };\n";
+
+/// A scratch directory removed when dropped.
+struct Dir(PathBuf);
+
+impl Dir {
+    fn new(name: &str) -> Dir {
+        let dir = std::env::temp_dir().join(format!("dwm-cheatsheet-diff-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        Dir(dir)
+    }
+
+    fn write(&self, name: &str, contents: &str) {
+        fs::write(self.0.join(name), contents).unwrap();
+    }
+}
+
+impl Drop for Dir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn diff(dir: &Path, args: &[&str]) -> Output {
+    Command::new(BIN)
+        .current_dir(dir)
+        .arg("diff")
+        .args(args)
+        .output()
+        .expect("run dwm-cheatsheet")
+}
+
+#[test]
+fn diff_reports_changed_bindings_and_settings() {
+    let dir = Dir::new("json");
+    dir.write("old.h", OLD);
+    dir.write("new.h", NEW);
+
+    let output = diff(&dir.0, &["old.h", "new.h", "--format", "json"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let bindings: Vec<(&str, &str)> = json["bindings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|change| (change["change"].as_str().unwrap(), change["combo"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        bindings,
+        [("changed", "Super+Shift+Q"), ("added", "Super+f"), ("removed", "Super+b")]
+    );
+    let rebound = &json["bindings"][0];
+    assert_eq!(rebound["old"]["function"], "killclient");
+    assert_eq!(rebound["new"]["function"], "quit");
+    // Not the built-in "Launch Firefox browser" row for Super+b
+    assert_eq!(json["bindings"][2]["old"]["description"], "Show or hide the bar");
+
+    let settings: Vec<(&str, &str)> = json["settings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|change| (change["change"].as_str().unwrap(), change["name"].as_str().unwrap()))
+        .collect();
+    assert_eq!(settings, [("changed", "borderpx"), ("added", "gappih")]);
+    assert_eq!(json["settings"][0]["old"], "3");
+    assert_eq!(json["settings"][0]["new"], "2");
+}
+
+#[test]
+fn diff_reads_git_revisions() {
+    let dir = Dir::new("git");
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .current_dir(&dir.0)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+    };
+    if git(&["init", "-q"]).is_err() {
+        eprintln!("skipping: git is not installed");
+        return;
+    }
+    dir.write("config.h", OLD);
+    git(&["add", "config.h"]).unwrap();
+    git(&["commit", "-q", "-m", "old"]).unwrap();
+    dir.write("config.h", NEW);
+
+    let output = diff(&dir.0, &["git:HEAD:config.h", "config.h"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.starts_with("--- git:HEAD:config.h\n+++ config.h\n"), "{}", text);
+    assert!(text.contains("+ Super+f"), "{}", text);
+    assert!(text.contains("~ borderpx"), "{}", text);
+
+    let output = diff(&dir.0, &["git:nope:config.h", "config.h"]);
+    assert_eq!(output.status.code(), Some(1));
+}
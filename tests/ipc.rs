@@ -0,0 +1,282 @@
+//! Talks to a fake dwm-ipc server over a Unix socket, so no dwm is needed.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+const BIN: &str = env!("CARGO_BIN_EXE_dwm-cheatsheet");
+
+const MONITORS: &str = r#"[
+  {"master_factor": 0.55, "num_master": 1, "num": 0, "is_selected": false,
+   "monitor_geometry": {"x": 0, "y": 0, "width": 1920, "height": 1080},
+   "tagset": {"current": 1, "old": 1}, "clients": {"selected": 0, "stack": [], "all": []},
+   "layout": {"symbol": {"current": "HHH", "old": "[]="}, "address": {"current": 1, "old": 2}}},
+  {"master_factor": 0.55, "num_master": 1, "num": 1, "is_selected": true,
+   "monitor_geometry": {"x": 1920, "y": 0, "width": 2560, "height": 1440},
+   "tagset": {"current": 5, "old": 1}, "clients": {"selected": 6291462, "stack": [6291462], "all": [6291462]},
+   "layout": {"symbol": {"current": "><>", "old": "[]="}, "address": {"current": 3, "old": 2}}}
+]"#;
+
+const TAGS: &str = r#"[
+  {"bit_mask": 1, "name": "1"}, {"bit_mask": 2, "name": "2"},
+  {"bit_mask": 4, "name": "3"}, {"bit_mask": 8, "name": "4"}
+]"#;
+
+const LAYOUTS: &str = r#"[
+  {"symbol": "[]=", "address": 94558473290016}, {"symbol": "><>", "address": 94558473290032}
+]"#;
+
+/// Functions the fake dwm registered in `ipccommands[]`.
+const IPC_COMMANDS: [&str; 4] = ["view", "togglegaps", "incrgaps", "setlayoutsafe"];
+
+const CLIENT: &str = r#"{
+  "name": "Firefox", "tags": 5, "window_id": 6291462, "monitor_number": 1,
+  "states": {"is_fixed": false, "is_floating": true, "is_urgent": false,
+             "never_focus": false, "old_state": false, "is_fullscreen": false}
+}"#;
+
+/// A stand-in for dwm's IPC socket that answers with canned replies and records
+/// every message it receives as `(type, payload)`.
+struct FakeDwm {
+    dir: PathBuf,
+    socket: PathBuf,
+    received: Arc<Mutex<Vec<(u8, String)>>>,
+}
+
+impl FakeDwm {
+    fn start(name: &str) -> FakeDwm {
+        let dir = std::env::temp_dir().join(format!("dwm-cheatsheet-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let socket = dir.join("dwm.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let log = Arc::clone(&received);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let log = Arc::clone(&log);
+                thread::spawn(move || serve(stream.unwrap(), log));
+            }
+        });
+        FakeDwm { dir, socket, received }
+    }
+
+    /// A config.h that points the cheatsheet at this server.
+    fn config(&self) -> PathBuf {
+        let config = self.dir.join("config.h");
+        fs::write(
+            &config,
+            format!(
+                "#define MODKEY Mod4Mask\n\
+                 static const char *ipcsockpath = \"{}\";\n\
+                 static const Layout layouts[] = {{\n\
+                     {{\"[]=\", tile}},\n\
+                     {{\"><>\", NULL}},\n\
+                     {{NULL, NULL}},\n\
+                 }};\n\
+                 static const Key keys[] = {{\n\
+                     {{MODKEY, XK_2, view, {{.ui = 1 << 1}}}},\n\
+                     {{MODKEY, XK_Tab, view, {{0}}}},\n\
+                     {{MODKEY, XK_a, togglegaps, {{0}}}},\n\
+                     {{MODKEY, XK_minus, incrgaps, {{.i = -3}}}},\n\
+                     {{MODKEY | ShiftMask, XK_f, setlayout, {{.v = &layouts[1]}}}},\n\
+                     {{MODKEY, XK_b, togglebar, {{0}}}},\n\
+                     {{MODKEY | ShiftMask, XK_q, killclient, {{0}}}},\n\
+                     {{MODKEY, XK_Return, spawn, {{.v = termcmd}}}},\n\
+                 }};\n",
+                self.socket.display()
+            ),
+        )
+        .unwrap();
+        config
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        Command::new(BIN)
+            .arg("--config")
+            .arg(self.config())
+            .args(args)
+            .output()
+            .expect("run dwm-cheatsheet")
+    }
+}
+
+impl Drop for FakeDwm {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn serve(mut stream: UnixStream, log: Arc<Mutex<Vec<(u8, String)>>>) {
+    loop {
+        let mut header = [0; 12];
+        if stream.read_exact(&mut header).is_err() {
+            return;
+        }
+        assert_eq!(&header[..7], b"DWM-IPC");
+        let size = u32::from_ne_bytes(header[7..11].try_into().unwrap()) as usize;
+        let kind = header[11];
+        let mut payload = vec![0; size];
+        stream.read_exact(&mut payload).unwrap();
+        log.lock()
+            .unwrap()
+            .push((kind, String::from_utf8(payload.clone()).unwrap()));
+
+        let reply = match kind {
+            0 => {
+                let command: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+                if IPC_COMMANDS.contains(&command["command"].as_str().unwrap()) {
+                    r#"{"result": "success"}"#
+                } else {
+                    r#"{"result": "error", "reason": "Command not found"}"#
+                }
+            }
+            1 => MONITORS,
+            2 => TAGS,
+            3 => LAYOUTS,
+            4 => CLIENT,
+            _ => r#"{"result": "error", "reason": "Invalid message type"}"#,
+        };
+        // dwm sends the JSON NUL-terminated
+        let mut message = b"DWM-IPC".to_vec();
+        message.extend_from_slice(&(reply.len() as u32 + 1).to_ne_bytes());
+        message.push(kind);
+        message.extend_from_slice(reply.as_bytes());
+        message.push(0);
+        stream.write_all(&message).unwrap();
+    }
+}
+
+#[test]
+fn state_reports_the_selected_monitor() {
+    let dwm = FakeDwm::start("state");
+    let output = dwm.run(&["state"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "monitor: 1\n\
+         tags: 1 3\n\
+         layout: ><>\n\
+         focused: Firefox\n\
+         floating: true\n\
+         fullscreen: false\n"
+    );
+
+    let received = dwm.received.lock().unwrap();
+    let kinds: Vec<u8> = received.iter().map(|(kind, _)| *kind).collect();
+    assert_eq!(kinds, [1, 2, 4]);
+    assert_eq!(received[2].1, r#"{"client_window_id":6291462}"#);
+}
+
+#[test]
+fn state_fails_without_dwm() {
+    let dwm = FakeDwm::start("missing");
+    let config = dwm.config();
+    fs::remove_file(&dwm.socket).unwrap();
+
+    let output = Command::new(BIN)
+        .arg("--config")
+        .arg(&config)
+        .arg("state")
+        .output()
+        .expect("run dwm-cheatsheet");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("dwm.sock"), "{}", stderr);
+}
+
+/// The `run_command` payloads the fake dwm received.
+fn commands(dwm: &FakeDwm) -> Vec<serde_json::Value> {
+    dwm.received
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(kind, _)| *kind == 0)
+        .map(|(_, payload)| serde_json::from_str(payload).unwrap())
+        .collect()
+}
+
+#[test]
+fn run_command_sends_function_and_argument() {
+    let dwm = FakeDwm::start("run-command");
+    for combo in ["Super+2", "Super+Tab", "super+A", "Super+minus", "Shift+Super+f"] {
+        let output = dwm.run(&["run-command", combo]);
+        assert!(output.status.success(), "{}: {}", combo, String::from_utf8_lossy(&output.stderr));
+    }
+
+    assert_eq!(
+        commands(&dwm),
+        [
+            serde_json::json!({"command": "view", "args": [2]}),
+            // dwm-ipc takes `view`'s `{0}` as the number 0, not as no argument
+            serde_json::json!({"command": "view", "args": [0]}),
+            serde_json::json!({"command": "togglegaps", "args": []}),
+            serde_json::json!({"command": "incrgaps", "args": [-3]}),
+            serde_json::json!({"command": "setlayoutsafe", "args": [94558473290032u64]}),
+        ]
+    );
+}
+
+#[test]
+fn run_command_reports_dwm_errors() {
+    let dwm = FakeDwm::start("run-command-error");
+    let output = dwm.run(&["run-command", "Super+b"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("dwm refused togglebar: Command not found"), "{}", stderr);
+    assert_eq!(commands(&dwm), [serde_json::json!({"command": "togglebar", "args": []})]);
+}
+
+#[test]
+fn run_command_rejects_spawn_bindings() {
+    let dwm = FakeDwm::start("run-command-spawn");
+    let output = dwm.run(&["run-command", "Super+Return"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("spawn can't be run over IPC"), "{}", stderr);
+    assert!(commands(&dwm).is_empty());
+}
+
+#[test]
+fn run_command_rejects_client_functions() {
+    let dwm = FakeDwm::start("run-command-client");
+    let output = dwm.run(&["run-command", "Super+Shift+q"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("killclient can't be run over IPC"), "{}", stderr);
+    assert!(commands(&dwm).is_empty());
+}
+
+#[test]
+fn state_rejects_an_oversized_reply() {
+    let dwm = FakeDwm::start("oversized");
+    let config = dwm.config();
+    // Swap the fake for one announcing a 4 GiB reply it never sends
+    fs::remove_file(&dwm.socket).unwrap();
+    let listener = UnixListener::bind(&dwm.socket).unwrap();
+    thread::spawn(move || {
+        let mut stream = listener.incoming().next().unwrap().unwrap();
+        let mut header = [0; 12];
+        stream.read_exact(&mut header).unwrap();
+        let mut message = b"DWM-IPC".to_vec();
+        message.extend_from_slice(&u32::MAX.to_ne_bytes());
+        message.push(header[11]);
+        stream.write_all(&message).unwrap();
+        thread::sleep(std::time::Duration::from_secs(1));
+    });
+
+    let output = Command::new(BIN)
+        .arg("--config")
+        .arg(&config)
+        .arg("state")
+        .output()
+        .expect("run dwm-cheatsheet");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("implausibly large"), "{}", stderr);
+}
@@ -0,0 +1,114 @@
+//! Replays a combo with `dwm-cheatsheet send-keys` against a private Xvfb and checks
+//! that a client holding a dwm-style passive grab receives it.
+
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{ConnectionExt as _, GrabMode, KeyButMask, ModMask};
+use x11rb::protocol::Event;
+
+const BIN: &str = env!("CARGO_BIN_EXE_dwm-cheatsheet");
+
+/// An Xvfb server that is killed when dropped.
+struct Xvfb {
+    child: Child,
+    display: String,
+}
+
+impl Xvfb {
+    /// Starts Xvfb on a free display, or `None` when it isn't installed.
+    fn start() -> Option<Xvfb> {
+        let mut child = Command::new("Xvfb")
+            .args(["-displayfd", "1", "-nolisten", "tcp", "-screen", "0", "640x480x24"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+
+        // Xvfb writes the display number it picked once it accepts connections
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().expect("stdout is piped"))
+            .read_line(&mut line)
+            .expect("read display number from Xvfb");
+        let display = format!(":{}", line.trim());
+        Some(Xvfb { child, display })
+    }
+}
+
+impl Drop for Xvfb {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+#[ignore = "needs Xvfb; run with cargo test -- --ignored"]
+fn send_keys_reaches_a_passive_grab() {
+    let xvfb = Xvfb::start().expect("start Xvfb");
+
+    // Grab Super+Shift+f on the root window the way dwm's grabkeys() does
+    let (conn, screen) = x11rb::connect(Some(&xvfb.display)).expect("connect to Xvfb");
+    let root = conn.setup().roots[screen].root;
+    let setup = conn.setup();
+    let mapping = conn
+        .get_keyboard_mapping(setup.min_keycode, setup.max_keycode - setup.min_keycode + 1)
+        .unwrap()
+        .reply()
+        .unwrap();
+    let offset = mapping
+        .keysyms
+        .chunks(mapping.keysyms_per_keycode as usize)
+        .position(|syms| syms[0] == u32::from(b'f'))
+        .expect("Xvfb maps a key to 'f'");
+    let keycode = setup.min_keycode + offset as u8;
+    conn.grab_key(
+        true,
+        root,
+        ModMask::M4 | ModMask::SHIFT,
+        keycode,
+        GrabMode::ASYNC,
+        GrabMode::ASYNC,
+    )
+    .unwrap()
+    .check()
+    .expect("grab Super+Shift+f");
+
+    let status = Command::new(BIN)
+        .args(["send-keys", "Super+Shift+f"])
+        .env("DISPLAY", &xvfb.display)
+        .status()
+        .expect("run dwm-cheatsheet");
+    assert!(status.success(), "send-keys exited with {}", status);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        match conn.poll_for_event().unwrap() {
+            Some(Event::KeyPress(event)) => {
+                assert_eq!(event.detail, keycode);
+                let state = u16::from(event.state);
+                assert_eq!(
+                    state & u16::from(KeyButMask::MOD4 | KeyButMask::SHIFT),
+                    u16::from(KeyButMask::MOD4 | KeyButMask::SHIFT)
+                );
+                return;
+            }
+            Some(_) => {}
+            None => std::thread::sleep(Duration::from_millis(10)),
+        }
+    }
+    panic!("the grabbing client never saw Super+Shift+f");
+}
+
+#[test]
+fn send_keys_rejects_unknown_keysyms() {
+    let output = Command::new(BIN)
+        .args(["send-keys", "Super+NoSuchKey"])
+        .env_remove("DISPLAY")
+        .output()
+        .expect("run dwm-cheatsheet");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown keysym 'NoSuchKey'"), "{}", stderr);
+}